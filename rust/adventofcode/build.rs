@@ -0,0 +1,157 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `yNNNN`, where `NNNN` is the puzzle year
+fn is_year_module(name: &str) -> bool {
+    name.len() == "yNNNN".len()
+        && name.starts_with('y')
+        && name[1..].bytes().all(|b| b.is_ascii_digit())
+}
+
+/// `dayNN.rs`, where `NN` is exactly two digits
+fn is_day_module(name: &str) -> bool {
+    let bytes = name.as_bytes();
+
+    bytes.len() == "dayNN.rs".len()
+        && name.starts_with("day")
+        && name.ends_with(".rs")
+        && bytes[3].is_ascii_digit()
+        && bytes[4].is_ascii_digit()
+}
+
+/// Anything that looks like a top level `Args` type definition
+fn defines_args(source: &str) -> bool {
+    source.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with("pub enum Args") || line.starts_with("pub struct Args")
+    })
+}
+
+/// Sorted entries of `dir` whose file names satisfy `filter`
+fn entries(dir: &Path, filter: fn(&str) -> bool) -> Vec<PathBuf> {
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut paths = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Reading {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("Reading directory entry").path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(filter)
+        })
+        .collect::<Vec<_>>();
+
+    paths.sort();
+    paths
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().unwrap().to_str().unwrap().to_string()
+}
+
+/// Whether `source` has a `mod name;` (or `pub mod name;`) line
+fn declares(source: &str, name: &str) -> bool {
+    source.lines().any(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("pub ").unwrap_or(line);
+        line.strip_prefix("mod ")
+            .and_then(|rest| rest.strip_suffix(';'))
+            .is_some_and(|declared| declared.trim() == name)
+    })
+}
+
+fn read_declarations(path: &Path) -> Option<String> {
+    println!("cargo:rerun-if-changed={}", path.display());
+    fs::read_to_string(path).ok()
+}
+
+/// Items `include!`d at the end of `src/yNNNN/mod.rs`, whose `mod` lines are kept up to date by
+/// `new-day` so rustfmt and editors can follow them
+fn year_module(dir: &Path, year: &str) -> String {
+    let mut generated = String::from("use clap::Clap;\n\nuse crate::command::Command;\n\n");
+    let mut names = Vec::new();
+
+    let declarations = read_declarations(&dir.join("mod.rs")).unwrap_or_default();
+
+    for path in entries(dir, is_day_module) {
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let name = file_stem(&path);
+        let source = fs::read_to_string(&path).expect("Reading solution module");
+
+        if !declares(&declarations, &name) {
+            generated.push_str(&format!(
+                "compile_error!(\"src/y{0}/{1}.rs isn't declared, add `mod {1};` to \
+                 src/y{0}/mod.rs\");\n",
+                year, name
+            ));
+        }
+
+        if !defines_args(&source) {
+            generated.push_str(&format!(
+                "compile_error!(\"src/y{}/{}.rs must define `pub enum Args` (or `pub struct Args`) \
+                 deriving `Clap` and implementing `Command`\");\n",
+                year, name
+            ));
+        }
+
+        names.push(name);
+    }
+
+    if names.is_empty() {
+        generated.push_str(&format!(
+            "compile_error!(\"No src/y{}/dayNN.rs solution modules found\");\n",
+            year
+        ));
+    } else {
+        generated.push_str(&format!(
+            "pub const YEAR: u16 = {};\n\n\
+             fn input(name: &str) -> anyhow::Result<String> {{\n    crate::input(YEAR, name)\n}}\n\n\
+             #[allow(dead_code)]\n\
+             fn number_input<T>(name: &str, given: Option<T>) -> anyhow::Result<T>\n\
+             where\n    T: std::str::FromStr,\n    T::Err: std::error::Error + Send + Sync + 'static,\n\
+             {{\n    crate::number_input(YEAR, name, given)\n}}\n\n\
+             solution!({});\n",
+            year,
+            names.join(", ")
+        ));
+    }
+
+    generated
+}
+
+fn main() {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).to_path_buf();
+
+    let declarations = read_declarations(&src.join("main.rs")).unwrap_or_default();
+    let mut generated = String::new();
+    let mut years = Vec::new();
+
+    for dir in entries(&src, is_year_module) {
+        let module = file_stem(&dir);
+        let year = module[1..].to_string();
+
+        if !declares(&declarations, &module) {
+            generated.push_str(&format!(
+                "compile_error!(\"src/{0}/ isn't declared, add `mod {0};` to src/main.rs\");\n",
+                module
+            ));
+        }
+
+        let path = out.join(format!("{}.rs", module));
+        fs::write(&path, year_module(&dir, &year))
+            .unwrap_or_else(|e| panic!("Writing {}: {}", path.display(), e));
+
+        years.push(format!("{} => \"{}\"", module, year));
+    }
+
+    if years.is_empty() {
+        generated.push_str("compile_error!(\"No src/yNNNN/ year directories found\");\n");
+    } else {
+        generated.push_str(&format!("years!({});\n", years.join(", ")));
+    }
+
+    fs::write(out.join("years.rs"), generated).expect("Writing years.rs");
+}
@@ -1,12 +1,21 @@
 use clap::Clap;
 use tracing_subscriber::EnvFilter;
 
-use crate::Solutions;
+use crate::output::Format;
+use crate::Years;
 
 macro_rules! solution {
     ($($day:ident),+) => {
         $(
-            mod $day;
+            const _: fn() = || {
+                fn solution_args_must_derive_clap_and_implement_command<T>()
+                where
+                    T: Clap + crate::command::Command,
+                {
+                }
+
+                solution_args_must_derive_clap_and_implement_command::<$day::Args>();
+            };
         )+
 
         #[derive(Debug, Clap)]
@@ -15,7 +24,7 @@ macro_rules! solution {
             #[allow(non_camel_case_types)]
             $day {
                 #[clap(subcommand)]
-                contents: crate::$day::Args,
+                contents: $day::Args,
             },
             )+
         }
@@ -27,6 +36,35 @@ macro_rules! solution {
                 }
             }
         }
+
+        pub const DAYS: &[&str] = &[$(stringify!($day)),+];
+    }
+}
+
+macro_rules! years {
+    ($($year:ident => $name:literal),+) => {
+        #[derive(Debug, Clap)]
+        pub enum Years {
+            $(
+            #[allow(non_camel_case_types)]
+            #[clap(name = $name)]
+            $year {
+                #[clap(subcommand)]
+                contents: crate::$year::Solutions,
+            },
+            )+
+        }
+
+        impl Command for Years {
+            fn execute(&self) -> anyhow::Result<String> {
+                match self {
+                    $(Self::$year { contents } => contents.execute(),)+
+                }
+            }
+        }
+
+        /// Every registered year along with its solution modules
+        pub const YEARS: &[(&str, &[&str])] = &[$(($name, crate::$year::DAYS)),+];
     }
 }
 
@@ -35,8 +73,30 @@ pub struct Args {
     #[clap(long, default_value = "warn")]
     pub logging_filter: String,
 
+    /// Output format for solution results
+    #[clap(long, global = true, default_value = "text", possible_values = &["text", "json", "tsv"])]
+    pub format: Format,
+
     #[clap(subcommand)]
-    pub command: Solutions,
+    pub command: Commands,
+}
+
+#[derive(Debug, Clap)]
+pub enum Commands {
+    /// Create a new solution module from `solution.rs.skel`
+    NewDay(crate::new_day::NewDay),
+
+    /// Download a puzzle input, unless it is already cached
+    Fetch(crate::fetch::Fetch),
+
+    /// Run a solution and submit its answer
+    Submit(crate::submit::Submit),
+
+    /// Run both parts of every registered solution
+    RunAll(crate::run_all::RunAll),
+
+    #[clap(flatten)]
+    Year(Years),
 }
 
 impl Args {
@@ -0,0 +1,304 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Clap;
+use tracing::debug;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+/// Where the time of the last request is kept, so the delay holds across runs
+const LAST_REQUEST: &str = "inputs/.last-request";
+
+/// Options shared by every subcommand that talks to the Advent of Code site
+#[derive(Debug, Clap)]
+pub struct ClientArgs {
+    /// Site to talk to, defaults to $AOC_BASE_URL or https://adventofcode.com
+    #[clap(long)]
+    base_url: Option<String>,
+
+    /// File holding the session cookie when $AOC_SESSION is not set,
+    /// defaults to ~/.config/adventofcode/session
+    #[clap(long)]
+    session_file: Option<PathBuf>,
+
+    /// Minimum number of seconds between requests
+    #[clap(long, default_value = "5")]
+    min_delay: u64,
+}
+
+impl ClientArgs {
+    pub fn client(&self) -> Result<Client> {
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.clone(),
+            None => std::env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+        };
+
+        Ok(Client::new(
+            &base_url,
+            &session(self.session_file.as_deref())?,
+            Duration::from_secs(self.min_delay),
+            Path::new(LAST_REQUEST),
+        ))
+    }
+}
+
+/// The session cookie from $AOC_SESSION, or else the session file
+fn session(session_file: Option<&Path>) -> Result<String> {
+    if let Ok(session) = std::env::var(SESSION_ENV) {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = match session_file {
+        Some(path) => path.to_path_buf(),
+        None => std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .ok_or_else(|| anyhow!("${} is not set and no config directory found", SESSION_ENV))?
+            .join("adventofcode")
+            .join("session"),
+    };
+
+    let session = std::fs::read_to_string(&path)
+        .with_context(|| format!("${} is not set, reading {}", SESSION_ENV, path.display()))?;
+
+    Ok(session.trim().to_string())
+}
+
+/// A polite Advent of Code client
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    min_delay: Duration,
+    last_request: PathBuf,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, min_delay: Duration, last_request: &Path) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            min_delay,
+            last_request: last_request.to_path_buf(),
+        }
+    }
+
+    /// Sleep until `min_delay` has passed since the previous request, then record this one
+    fn wait_for_turn(&self) -> Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
+        let previous = std::fs::read_to_string(&self.last_request)
+            .ok()
+            .and_then(|millis| millis.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+
+        if let Some(previous) = previous {
+            if let Some(wait) = (previous + self.min_delay).checked_sub(now) {
+                debug!(?wait, "Waiting before next request");
+                std::thread::sleep(wait);
+            }
+        }
+
+        if let Some(parent) = self.last_request.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Rounded up, as a time recorded early would cut the next wait short
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let millis = now.as_nanos().div_ceil(1_000_000);
+        std::fs::write(&self.last_request, millis.to_string())
+            .with_context(|| format!("Writing {}", self.last_request.display()))
+    }
+
+    fn request(&self, method: &str, path: &str) -> Result<ureq::Request> {
+        self.wait_for_turn()?;
+
+        let url = format!("{}{}", self.base_url, path);
+        debug!(method, url = url.as_str(), "Request");
+
+        Ok(self
+            .agent
+            .request(method, &url)
+            .set("Cookie", &format!("session={}", self.session)))
+    }
+
+    fn body(response: std::result::Result<ureq::Response, ureq::Error>) -> Result<String> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => Err(anyhow!(
+                "{} returned {}: {}",
+                response.get_url().to_string(),
+                status,
+                response.into_string().unwrap_or_default().trim()
+            )),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String> {
+        Self::body(self.request("GET", path)?.call())
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        Self::body(self.request("POST", path)?.send_form(form))
+    }
+}
+
+/// A throwaway HTTP server standing in for the real site in tests
+#[cfg(test)]
+pub mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread::JoinHandle;
+
+    /// A unique, empty scratch directory
+    pub fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("adventofcode-{}-{}", std::process::id(), name));
+
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Serve `responses` as `(status, body)` pairs, one per connection,
+    /// returning every request received once they have all been sent
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut request = String::new();
+                    let mut content_length = 0;
+
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+
+                        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = length.trim().parse().unwrap();
+                        }
+
+                        request.push_str(&line);
+
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                    }
+
+                    let mut form = vec![0; content_length];
+                    reader.read_exact(&mut form).unwrap();
+                    request.push_str(&String::from_utf8(form).unwrap());
+
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn test_get_sends_session_and_user_agent() -> Result<()> {
+        let dir = mock::scratch_dir("client-get");
+        let (base_url, server) = mock::serve(vec![(200, String::from("1\n2\n"))]);
+
+        let client = Client::new(
+            &base_url,
+            "abc123",
+            Duration::from_secs(0),
+            &dir.join(".last-request"),
+        );
+
+        assert_eq!(client.get("/2017/day/1/input")?, "1\n2\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2017/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+        assert!(requests[0].contains(&format!("User-Agent: {}\r\n", USER_AGENT)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_error_status() {
+        let dir = mock::scratch_dir("client-status");
+        let (base_url, server) = mock::serve(vec![(404, String::from("Not Found"))]);
+
+        let client = Client::new(
+            &base_url,
+            "abc123",
+            Duration::from_secs(0),
+            &dir.join("last"),
+        );
+
+        let error = client.get("/2017/day/26/input").unwrap_err().to_string();
+        assert!(error.contains("404"), "{}", error);
+        assert!(error.contains("Not Found"), "{}", error);
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_min_delay() -> Result<()> {
+        let dir = mock::scratch_dir("client-delay");
+        let (base_url, server) = mock::serve(vec![
+            (200, String::from("first")),
+            (200, String::from("second")),
+        ]);
+
+        let client = Client::new(
+            &base_url,
+            "abc123",
+            Duration::from_millis(300),
+            &dir.join("last"),
+        );
+
+        let start = Instant::now();
+        assert_eq!(client.get("/")?, "first");
+        assert_eq!(client.get("/")?, "second");
+        assert!(start.elapsed() >= Duration::from_millis(300));
+
+        server.join().unwrap();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::Point;
+
+/// Which way `y` grows, since puzzles disagree on whether "up" is `-y` or `+y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YAxis {
+    /// `y` grows downwards, as in the puzzle diagrams and `Grid`
+    Down,
+    /// `y` grows upwards, as on a graph
+    Up,
+}
+
+impl YAxis {
+    fn apply(self, delta: Point) -> Point {
+        match self {
+            Self::Down => delta,
+            Self::Up => delta.reflect_x(),
+        }
+    }
+}
+
+/// One of the four orthogonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from `Up`
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    /// A quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self as usize + 1)
+    }
+
+    /// A quarter turn counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self as usize + 3)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self as usize + 2)
+    }
+
+    /// The unit step in this direction, with `y` growing downwards
+    pub fn delta(self) -> Point {
+        self.delta_in(YAxis::Down)
+    }
+
+    /// The unit step in this direction under the given `y` convention
+    pub fn delta_in(self, y_axis: YAxis) -> Point {
+        Heading::from(self).delta_in(y_axis)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = Error;
+
+    /// `U`/`D`/`L`/`R`, `N`/`E`/`S`/`W` in either case, or an arrow such as `^` or `→`
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            'U' | 'u' | 'N' | 'n' | '^' | '↑' => Ok(Self::Up),
+            'R' | 'r' | 'E' | 'e' | '>' | '→' => Ok(Self::Right),
+            'D' | 'd' | 'S' | 's' | 'v' | 'V' | '↓' => Ok(Self::Down),
+            'L' | 'l' | 'W' | 'w' | '<' | '←' => Ok(Self::Left),
+            _ => Err(anyhow!("Unknown direction: {:?}", c)),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.trim().chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(anyhow!("Unknown direction: {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Heading::from(*self).fmt(f)
+    }
+}
+
+/// One of the eight compass headings, including the diagonals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Heading {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Heading {
+    /// Every heading, clockwise from `North`
+    pub const ALL: [Heading; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    const NAMES: [&'static str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    /// An eighth of a turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self as usize + 1)
+    }
+
+    /// An eighth of a turn counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self as usize + 7)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self as usize + 4)
+    }
+
+    /// Whether this is one of the four orthogonal headings
+    pub fn is_orthogonal(self) -> bool {
+        (self as usize).is_multiple_of(2)
+    }
+
+    /// The unit step in this heading, with `y` growing downwards
+    pub fn delta(self) -> Point {
+        self.delta_in(YAxis::Down)
+    }
+
+    /// The unit step in this heading under the given `y` convention
+    pub fn delta_in(self, y_axis: YAxis) -> Point {
+        let delta = match self {
+            Self::North => Point::new(0, -1),
+            Self::NorthEast => Point::new(1, -1),
+            Self::East => Point::new(1, 0),
+            Self::SouthEast => Point::new(1, 1),
+            Self::South => Point::new(0, 1),
+            Self::SouthWest => Point::new(-1, 1),
+            Self::West => Point::new(-1, 0),
+            Self::NorthWest => Point::new(-1, -1),
+        };
+
+        y_axis.apply(delta)
+    }
+}
+
+impl From<Direction> for Heading {
+    fn from(direction: Direction) -> Self {
+        Self::from_index(direction as usize * 2)
+    }
+}
+
+impl TryFrom<Heading> for Direction {
+    type Error = Error;
+
+    fn try_from(heading: Heading) -> Result<Self> {
+        if !heading.is_orthogonal() {
+            return Err(anyhow!("{} isn't an orthogonal direction", heading));
+        }
+
+        Ok(Self::from_index(heading as usize / 2))
+    }
+}
+
+impl TryFrom<char> for Heading {
+    type Error = Error;
+
+    /// Any `Direction` character, or a diagonal arrow such as `↗`
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '↗' => Ok(Self::NorthEast),
+            '↘' => Ok(Self::SouthEast),
+            '↙' => Ok(Self::SouthWest),
+            '↖' => Ok(Self::NorthWest),
+            _ => Direction::try_from(c).map(Self::from),
+        }
+    }
+}
+
+impl FromStr for Heading {
+    type Err = Error;
+
+    /// A compass abbreviation such as `N` or `sw`, or a single direction character
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+
+        if let Some(index) = Self::NAMES
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(s))
+        {
+            return Ok(Self::ALL[index]);
+        }
+
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(anyhow!("Unknown heading: {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Heading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::NAMES[*self as usize])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_direction_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+
+        for direction in Direction::ALL.iter().copied() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(
+                direction.turn_right().delta(),
+                direction.delta().rotate_right()
+            );
+            assert_eq!(direction.reverse().delta(), direction.delta() * -1);
+        }
+    }
+
+    #[test]
+    fn test_direction_delta() {
+        assert_eq!(Direction::Up.delta(), Point::new(0, -1));
+        assert_eq!(Direction::Up.delta_in(YAxis::Up), Point::new(0, 1));
+        assert_eq!(Direction::Right.delta_in(YAxis::Up), Point::new(1, 0));
+        assert_eq!(
+            Direction::ALL.iter().map(|d| d.delta()).collect::<Vec<_>>(),
+            Point::default().neighbors4().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_direction_parse() -> Result<()> {
+        assert_eq!(Direction::try_from('U')?, Direction::Up);
+        assert_eq!(Direction::try_from('w')?, Direction::Left);
+        assert_eq!(Direction::try_from('v')?, Direction::Down);
+        assert_eq!(Direction::try_from('→')?, Direction::Right);
+        assert_eq!(" ^ ".parse::<Direction>()?, Direction::Up);
+        assert!(Direction::try_from('x').is_err());
+        assert!("UR".parse::<Direction>().is_err());
+        assert!("".parse::<Direction>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_heading() -> Result<()> {
+        assert_eq!(Heading::North.turn_right(), Heading::NorthEast);
+        assert_eq!(Heading::North.turn_left(), Heading::NorthWest);
+        assert_eq!(Heading::SouthEast.reverse(), Heading::NorthWest);
+        assert_eq!(Heading::NorthEast.delta(), Point::new(1, -1));
+        assert_eq!(Heading::NorthEast.delta_in(YAxis::Up), Point::new(1, 1));
+
+        assert_eq!(Heading::from(Direction::Left), Heading::West);
+        assert_eq!(Direction::try_from(Heading::South)?, Direction::Down);
+        assert!(Direction::try_from(Heading::SouthWest).is_err());
+
+        for heading in Heading::ALL.iter().copied() {
+            assert_eq!(heading.delta().chebyshev(Point::default()), 1);
+            assert_eq!(heading.to_string().parse::<Heading>()?, heading);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_heading_parse() -> Result<()> {
+        assert_eq!("ne".parse::<Heading>()?, Heading::NorthEast);
+        assert_eq!("SW".parse::<Heading>()?, Heading::SouthWest);
+        assert_eq!("↖".parse::<Heading>()?, Heading::NorthWest);
+        assert_eq!("R".parse::<Heading>()?, Heading::East);
+        assert!("NNE".parse::<Heading>().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Clap;
+use tracing::debug;
+
+use crate::client::{Client, ClientArgs};
+use crate::Command;
+
+/// Download a puzzle input into `inputs/<year>/dayNN.txt`, unless it is already there
+#[derive(Debug, Clap)]
+pub struct Fetch {
+    /// Year of the puzzle, e.g. 2017
+    year: u16,
+
+    /// Day of the puzzle, 1 through 25
+    day: u8,
+
+    #[clap(flatten)]
+    client: ClientArgs,
+}
+
+impl Command for Fetch {
+    fn execute(&self) -> Result<String> {
+        let path = PathBuf::from(crate::input_path(self.year, &format!("day{:02}", self.day)));
+
+        if is_cached(&path) {
+            return Ok(format!("Already cached at {}", path.display()));
+        }
+
+        fetch(&self.client.client()?, self.year, self.day, &path)?;
+
+        Ok(format!("Downloaded to {}", path.display()))
+    }
+}
+
+/// An input counts as cached once it has contents; `new-day` leaves empty placeholders
+fn is_cached(path: &Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Download the input for `year` and `day` to `path`
+fn fetch(client: &Client, year: u16, day: u8, path: &Path) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(anyhow!("Day must be between 1 and 25: {}", day));
+    }
+
+    let input = client.get(&format!("/{}/day/{}/input", year, day))?;
+    debug!(len = input.len(), "Downloaded input");
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Creating {}", parent.display()))?;
+    }
+
+    std::fs::write(path, input).with_context(|| format!("Writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::mock;
+
+    #[test]
+    fn test_fetch() -> Result<()> {
+        let dir = mock::scratch_dir("fetch");
+        let (base_url, server) = mock::serve(vec![(200, String::from("0\n3\n0\n1\n-3\n"))]);
+        let client = Client::new(
+            &base_url,
+            "abc123",
+            Duration::from_secs(0),
+            &dir.join("last"),
+        );
+
+        let path = dir.join("2017").join("day05.txt");
+        assert!(!is_cached(&path));
+
+        fetch(&client, 2017, 5, &path)?;
+
+        assert!(is_cached(&path));
+        assert_eq!(std::fs::read_to_string(&path)?, "0\n3\n0\n1\n-3\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2017/day/5/input HTTP/1.1\r\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_cached() -> Result<()> {
+        let dir = mock::scratch_dir("fetch-cached");
+        let path = dir.join("day07.txt");
+
+        assert!(!is_cached(&path));
+
+        std::fs::write(&path, "")?;
+        assert!(!is_cached(&path));
+
+        std::fs::write(&path, "pbga (66)\n")?;
+        assert!(is_cached(&path));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_bad_day() {
+        let dir = mock::scratch_dir("fetch-bad-day");
+        let client = Client::new(
+            "http://127.0.0.1:9",
+            "",
+            Duration::from_secs(0),
+            &dir.join("last"),
+        );
+
+        assert!(fetch(&client, 2017, 26, &dir.join("day26.txt")).is_err());
+    }
+}
@@ -0,0 +1,415 @@
+//! Lines and polygons on the integer lattice
+
+use anyhow::{anyhow, Result};
+
+use crate::Point;
+
+/// The z component of the cross product of `a` and `b`
+fn cross(a: Point, b: Point) -> i64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Point, b: Point) -> i64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The lattice points closest to the straight line from `from` to `to`, inclusive of both
+/// ends, as drawn by Bresenham's algorithm
+pub fn line(from: Point, to: Point) -> Line {
+    let delta = to - from;
+
+    Line {
+        current: from,
+        end: to,
+        step: delta.signum(),
+        dx: delta.x.abs(),
+        dy: -delta.y.abs(),
+        error: delta.x.abs() - delta.y.abs(),
+        done: false,
+    }
+}
+
+/// Iterator returned by `line`, each point a king's move from the last
+#[derive(Debug, Clone)]
+pub struct Line {
+    current: Point,
+    end: Point,
+    step: Point,
+    dx: i64,
+    dy: i64,
+    error: i64,
+    done: bool,
+}
+
+impl Iterator for Line {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.done {
+            return None;
+        }
+
+        let pos = self.current;
+
+        if pos == self.end {
+            self.done = true;
+            return Some(pos);
+        }
+
+        let error = self.error * 2;
+
+        if error >= self.dy {
+            self.error += self.dy;
+            self.current.x += self.step.x;
+        }
+
+        if error <= self.dx {
+            self.error += self.dx;
+            self.current.y += self.step.y;
+        }
+
+        Some(pos)
+    }
+}
+
+/// A horizontal, vertical or 45° diagonal segment, inclusive of both ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    start: Point,
+    end: Point,
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Result<Self> {
+        let delta = (end - start).abs();
+
+        if delta.x != 0 && delta.y != 0 && delta.x != delta.y {
+            return Err(anyhow!(
+                "{} -> {} is neither axis-aligned nor diagonal",
+                start,
+                end
+            ));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    pub fn end(&self) -> Point {
+        self.end
+    }
+
+    /// The unit step from `start` towards `end`, zero for a single point
+    fn step(&self) -> Point {
+        (self.end - self.start).signum()
+    }
+
+    /// The number of steps from `start` to `end`
+    fn steps(&self) -> i64 {
+        self.start.chebyshev(self.end)
+    }
+
+    /// How many steps along the segment's line `pos` is from `start`, if it lies on that line
+    fn steps_to(&self, pos: Point) -> Option<i64> {
+        let offset = pos - self.start;
+        let step = self.step();
+
+        if step == Point::default() {
+            return if offset == step { Some(0) } else { None };
+        }
+
+        if cross(offset, step) != 0 {
+            return None;
+        }
+
+        // `offset` is a whole multiple of `step`, so projecting onto it is exact
+        Some(dot(offset, step) / dot(step, step))
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.steps_to(pos)
+            .is_some_and(|steps| (0..=self.steps()).contains(&steps))
+    }
+
+    /// Every point from `start` to `end`
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let start = self.start;
+        let step = self.step();
+
+        (0..=self.steps()).map(move |i| start + step * i)
+    }
+
+    /// The lattice points on both segments, ordered along `self`
+    pub fn intersection(&self, other: &Segment) -> Vec<Point> {
+        let (a, b) = (self.step(), other.step());
+
+        if a == Point::default() || b == Point::default() {
+            let (point, segment) = if a == Point::default() {
+                (self.start, other)
+            } else {
+                (other.start, self)
+            };
+
+            return if segment.contains(point) {
+                vec![point]
+            } else {
+                vec![]
+            };
+        }
+
+        let denominator = cross(a, b);
+
+        if denominator == 0 {
+            // Parallel, so they only meet if they share a line, along which they overlap
+            let (from, to) = match (self.steps_to(other.start), self.steps_to(other.end)) {
+                (Some(from), Some(to)) => (from.min(to).max(0), from.max(to).min(self.steps())),
+                _ => return vec![],
+            };
+
+            return (from..=to).map(|i| self.start + a * i).collect();
+        }
+
+        // Solve `self.start + a * s == other.start + b * t`, keeping only whole steps
+        let offset = other.start - self.start;
+        let s = cross(offset, b);
+        let t = cross(offset, a);
+
+        if s % denominator != 0 || t % denominator != 0 {
+            return vec![];
+        }
+
+        let (s, t) = (s / denominator, t / denominator);
+
+        if (0..=self.steps()).contains(&s) && (0..=other.steps()).contains(&t) {
+            vec![self.start + a * s]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// Twice the area enclosed by the polygon with the given vertices, by the shoelace formula
+///
+/// Doubling keeps the result exact, as lattice polygons can have half-integer areas.
+pub fn double_area(vertices: &[Point]) -> i64 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+
+    edges.map(|(a, b)| cross(*a, *b)).sum::<i64>().abs()
+}
+
+/// The number of lattice points on the polygon's edges
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+
+    edges
+        .map(|(a, b)| {
+            let delta = *b - *a;
+            gcd(delta.x, delta.y)
+        })
+        .sum()
+}
+
+/// The number of lattice points strictly inside a simple polygon, by Pick's theorem
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::rect::Rect;
+
+    fn segment() -> impl Strategy<Value = Segment> {
+        (-6i64..6, -6i64..6, -1i64..=1, -1i64..=1, 0i64..8).prop_map(|(x, y, dx, dy, len)| {
+            let start = Point::new(x, y);
+            Segment::new(start, start + Point::new(dx, dy) * len).unwrap()
+        })
+    }
+
+    /// A polygon that is star-shaped about the origin, so it is always simple
+    fn polygon() -> impl Strategy<Value = Vec<Point>> {
+        const SPOKES: [(i64, i64); 8] = [
+            (1, 0),
+            (2, 1),
+            (0, 1),
+            (-1, 2),
+            (-1, 0),
+            (-2, -1),
+            (0, -1),
+            (1, -2),
+        ];
+
+        prop::collection::vec(1i64..=4, SPOKES.len()).prop_map(|lengths| {
+            SPOKES
+                .iter()
+                .zip(lengths)
+                .map(|((x, y), length)| Point::new(*x, *y) * length)
+                .collect()
+        })
+    }
+
+    fn on_boundary(vertices: &[Point], pos: Point) -> bool {
+        vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .any(|(a, b)| cross(*b - *a, pos - *a) == 0 && Rect::new(*a, *b).contains(pos))
+    }
+
+    fn winding_number(vertices: &[Point], pos: Point) -> i64 {
+        let mut winding = 0;
+
+        for (a, b) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+            let side = cross(*b - *a, pos - *a);
+
+            if a.y <= pos.y && b.y > pos.y && side > 0 {
+                winding += 1;
+            } else if a.y > pos.y && b.y <= pos.y && side < 0 {
+                winding -= 1;
+            }
+        }
+
+        winding
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(
+            line(Point::new(0, 0), Point::new(5, 2)).collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 1),
+                Point::new(3, 1),
+                Point::new(4, 2),
+                Point::new(5, 2)
+            ]
+        );
+        assert_eq!(
+            line(Point::new(2, 2), Point::new(2, 2)).collect::<Vec<_>>(),
+            vec![Point::new(2, 2)]
+        );
+        assert_eq!(line(Point::new(3, 0), Point::new(0, -3)).count(), 4);
+    }
+
+    #[test]
+    fn test_line_brute_force() {
+        let area = Rect::new(Point::new(-4, -4), Point::new(4, 4));
+
+        for from in area.points() {
+            for to in area.points() {
+                let points = line(from, to).collect::<Vec<_>>();
+                let delta = to - from;
+                let major = delta.x.abs().max(delta.y.abs());
+
+                assert_eq!(points.first(), Some(&from));
+                assert_eq!(points.last(), Some(&to));
+                assert_eq!(points.len() as i64, major + 1);
+                assert!(points.windows(2).all(|w| w[0].chebyshev(w[1]) == 1));
+
+                // No point strays more than half a square from the true line along the minor axis
+                assert!(points
+                    .iter()
+                    .all(|p| 2 * cross(*p - from, delta).abs() <= major));
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment() -> Result<()> {
+        let segment = Segment::new(Point::new(0, 0), Point::new(3, -3))?;
+
+        assert!(segment.contains(Point::new(2, -2)));
+        assert!(!segment.contains(Point::new(4, -4)));
+        assert!(!segment.contains(Point::new(-1, 1)));
+        assert_eq!(segment.points().count(), 4);
+        assert!(Segment::new(Point::new(0, 0), Point::new(2, 1)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment_intersection() -> Result<()> {
+        let horizontal = Segment::new(Point::new(0, 2), Point::new(6, 2))?;
+        let vertical = Segment::new(Point::new(3, 0), Point::new(3, 5))?;
+        let diagonal = Segment::new(Point::new(0, 0), Point::new(1, 1))?;
+        let crossing = Segment::new(Point::new(0, 1), Point::new(1, 0))?;
+        let overlapping = Segment::new(Point::new(8, 2), Point::new(4, 2))?;
+
+        assert_eq!(horizontal.intersection(&vertical), vec![Point::new(3, 2)]);
+        assert_eq!(
+            horizontal.intersection(&overlapping),
+            vec![Point::new(4, 2), Point::new(5, 2), Point::new(6, 2)]
+        );
+
+        // These cross at (0.5, 0.5), which isn't a lattice point
+        assert_eq!(diagonal.intersection(&crossing), vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_polygon() {
+        let square = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(0, 4),
+        ];
+        let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 1)];
+
+        assert_eq!(double_area(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+
+        assert_eq!(double_area(&triangle), 3);
+        assert_eq!(boundary_points(&triangle), 5);
+        assert_eq!(interior_points(&triangle), 0);
+
+        let mut reversed = square;
+        reversed.reverse();
+        assert_eq!(double_area(&reversed), 32);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_segment_intersection(a in segment(), b in segment()) {
+            let expected = a.points()
+                .filter(|p| b.points().any(|q| q == *p))
+                .collect::<Vec<_>>();
+
+            prop_assert_eq!(a.intersection(&b), expected);
+            prop_assert!(a.points().all(|p| a.contains(p)));
+        }
+
+        #[test]
+        fn prop_polygon_brute_force(vertices in polygon()) {
+            let bounds = Rect::bounding(vertices.iter().copied()).unwrap();
+            let boundary = bounds
+                .points()
+                .filter(|p| on_boundary(&vertices, *p))
+                .collect::<HashSet<_>>();
+            let interior = bounds
+                .points()
+                .filter(|p| !boundary.contains(p) && winding_number(&vertices, *p) != 0)
+                .count();
+
+            prop_assert_eq!(boundary_points(&vertices), boundary.len() as i64);
+            prop_assert_eq!(interior_points(&vertices), interior as i64);
+        }
+    }
+}
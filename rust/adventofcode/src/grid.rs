@@ -0,0 +1,458 @@
+use std::fmt;
+use std::ops;
+
+use anyhow::{anyhow, Result};
+
+use crate::Point;
+
+/// A dense, row-major 2D grid addressed by `Point`, with `y` growing downwards
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from row-major `cells`
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if cells.len() != width * height {
+            return Err(anyhow!(
+                "{} cells can't fill a {}x{} grid",
+                cells.len(),
+                width,
+                height
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parse a character map, one line per row, mapping each character with `cell`
+    ///
+    /// Spaces are cells like any other, so lines aren't trimmed. Only a single blank line at
+    /// the end, as left by a final `\n\n`, is dropped.
+    pub fn parse<F>(map: &str, mut cell: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(map.len());
+
+        // `lines` already strips the `\r` of a `\r\n`
+        let mut lines = map.lines().collect::<Vec<_>>();
+
+        if lines.last() == Some(&"") {
+            lines.pop();
+        }
+
+        for line in lines {
+            let before = cells.len();
+
+            for c in line.chars() {
+                cells.push(cell(c)?);
+            }
+
+            let row_width = cells.len() - before;
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(anyhow!(
+                        "Row {} is {} wide, expected {}",
+                        height,
+                        row_width,
+                        width
+                    ))
+                }
+                _ => {}
+            }
+
+            height += 1;
+        }
+
+        Self::from_vec(width.unwrap_or(0), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn offset(&self, pos: Point) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y as usize * self.width + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.offset(pos).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.offset(pos).map(move |offset| &mut self.cells[offset])
+    }
+
+    /// The point `pos` lands on when the grid's edges wrap around
+    pub fn wrap(&self, pos: Point) -> Point {
+        pos.rem_euclid(Point::new(self.width as i64, self.height as i64))
+    }
+
+    /// The cell at `pos`, treating the grid as a torus; panics on an empty grid
+    pub fn get_wrapped(&self, pos: Point) -> &T {
+        &self[self.wrap(pos)]
+    }
+
+    pub fn get_wrapped_mut(&mut self, pos: Point) -> &mut T {
+        let pos = self.wrap(pos);
+        &mut self[pos]
+    }
+
+    /// Every point in row-major order
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        let height = self.height as i64;
+
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell along with its point, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The in-bounds orthogonal neighbours of `pos`
+    pub fn neighbors4(&self, pos: Point) -> impl Iterator<Item = (Point, &T)> {
+        pos.neighbors4()
+            .filter_map(move |neighbor| self.get(neighbor).map(|value| (neighbor, value)))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of `pos`
+    pub fn neighbors8(&self, pos: Point) -> impl Iterator<Item = (Point, &T)> {
+        pos.neighbors8()
+            .filter_map(move |neighbor| self.get(neighbor).map(|value| (neighbor, value)))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Build a `width` by `height` grid where each cell comes from the point `f` maps it to
+    fn remap<F>(&self, width: usize, height: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (from_x, from_y) = f(x, y);
+                self.cells[from_y * self.width + from_x].clone()
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Swap rows and columns
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotate a quarter turn clockwise
+    pub fn rotate_right(&self) -> Self {
+        let height = self.height;
+        self.remap(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotate a quarter turn counter-clockwise
+    pub fn rotate_left(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.remap(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.remap(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+}
+
+impl<T> ops::Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside {}x{}", pos, self.width, self.height))
+    }
+}
+
+impl<T> ops::IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside {}x{}", pos, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAP: &str = "#..\n.#.\n##.\n.#.\n";
+
+    fn parse_map(map: &str) -> Result<Grid<char>> {
+        Grid::parse(map, Ok)
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let grid = parse_map(MAP)?;
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid[Point::new(0, 0)], '#');
+        assert_eq!(grid[Point::new(1, 2)], '#');
+        assert_eq!(grid[Point::new(2, 3)], '.');
+
+        let walls = Grid::parse(MAP, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(anyhow!("Bad cell: {}", c)),
+        })?;
+        assert_eq!(walls.iter().filter(|(_, wall)| **wall).count(), 5);
+
+        assert!(parse_map("##\n#\n").is_err());
+        assert!(parse_map("##\n\n##\n").is_err());
+
+        // Spaces are cells, even at the ends of lines
+        let tubes = parse_map("  | \r\n  + \r\n    \r\n\r\n")?;
+        assert_eq!((tubes.width(), tubes.height()), (4, 3));
+        assert_eq!(tubes[Point::new(3, 2)], ' ');
+        assert_eq!(tubes[Point::new(2, 1)], '+');
+        assert!(Grid::parse("#?", |c| match c {
+            '#' => Ok(1),
+            _ => Err(anyhow!("Bad cell: {}", c)),
+        })
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get() -> Result<()> {
+        let mut grid = Grid::new(2, 3, 0);
+
+        assert!(grid.contains(Point::new(1, 2)));
+        assert!(!grid.contains(Point::new(2, 0)));
+        assert!(!grid.contains(Point::new(0, -1)));
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+
+        grid[Point::new(1, 2)] = 5;
+        *grid.get_mut(Point::new(0, 1)).unwrap() = 7;
+
+        assert_eq!(grid.get(Point::new(1, 2)), Some(&5));
+        assert_eq!(grid.get_mut(Point::new(-1, 0)), None);
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[0, 0], &[7, 0], &[0, 5]]
+        );
+
+        assert!(Grid::from_vec(2, 2, vec![1, 2, 3]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped() -> Result<()> {
+        let mut grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6])?;
+
+        assert_eq!(grid.wrap(Point::new(-1, 2)), Point::new(2, 0));
+        assert_eq!(*grid.get_wrapped(Point::new(3, -1)), 4);
+        assert_eq!(*grid.get_wrapped(Point::new(-4, -4)), 3);
+
+        *grid.get_wrapped_mut(Point::new(7, 7)) = 0;
+        assert_eq!(grid[Point::new(1, 1)], 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let grid = Grid::new(2, 2, 0);
+        let _ = grid[Point::new(2, 2)];
+    }
+
+    #[test]
+    fn test_rows_and_columns() -> Result<()> {
+        let grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6])?;
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+        assert_eq!(
+            grid.points().collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(0, 1),
+                Point::new(1, 1),
+                Point::new(2, 1),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbors() -> Result<()> {
+        let grid = Grid::from_vec(3, 3, (1..=9).collect())?;
+
+        let mut values = grid
+            .neighbors4(Point::new(1, 1))
+            .map(|(_, v)| *v)
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![2, 4, 6, 8]);
+
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors4(Point::new(2, 0)).count(), 2);
+        assert!(grid
+            .neighbors8(Point::new(2, 2))
+            .all(|(pos, v)| grid[pos] == *v));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transform() -> Result<()> {
+        let grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6])?;
+
+        assert_eq!(
+            grid.transpose(),
+            Grid::from_vec(2, 3, vec![1, 4, 2, 5, 3, 6])?
+        );
+        assert_eq!(
+            grid.rotate_right(),
+            Grid::from_vec(2, 3, vec![4, 1, 5, 2, 6, 3])?
+        );
+        assert_eq!(
+            grid.rotate_left(),
+            Grid::from_vec(2, 3, vec![3, 6, 2, 5, 1, 4])?
+        );
+        assert_eq!(
+            grid.flip_horizontal(),
+            Grid::from_vec(3, 2, vec![3, 2, 1, 6, 5, 4])?
+        );
+        assert_eq!(
+            grid.flip_vertical(),
+            Grid::from_vec(3, 2, vec![4, 5, 6, 1, 2, 3])?
+        );
+
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(
+            grid.rotate_right().rotate_right(),
+            grid.flip_horizontal().flip_vertical()
+        );
+        assert_eq!(grid.transpose().transpose(), grid);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> Result<()> {
+        let grid = parse_map(MAP)?;
+
+        assert_eq!(grid.to_string(), MAP.trim_end());
+        assert_eq!(
+            grid.map(|c| (*c == '#') as u8).to_string(),
+            "100\n010\n110\n010"
+        );
+
+        Ok(())
+    }
+}
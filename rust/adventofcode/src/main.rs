@@ -1,31 +1,70 @@
 #[macro_use]
 mod args;
+mod client;
 mod command;
+#[allow(dead_code)]
+mod direction;
+mod fetch;
+#[allow(dead_code)]
+mod geometry;
+#[allow(dead_code)]
+mod grid;
+mod new_day;
+mod output;
 mod point;
+#[allow(dead_code)]
+mod rect;
+mod rng;
+mod run_all;
+mod submit;
+#[allow(dead_code)]
+mod vector;
+mod y2017;
 
 use anyhow::{anyhow, Context, Result};
-use clap::Clap;
+use clap::{Clap, FromArgMatches, IntoApp};
 use tracing_subscriber::FmtSubscriber;
 
 use crate::command::Command;
+use crate::output::Record;
 use crate::point::Point;
 
-// NOTE: Each solution module must be added here
-solution!(day01, day02, day03, day04, day05, day06);
+// `Years` and `YEARS`, registering every `src/yNNNN/` module, generated by `build.rs`
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
 
-fn input(name: &str) -> Result<String> {
-    Ok(
-        std::fs::read_to_string(format!("inputs/{}.txt", name))
-            .with_context(|| name.to_string())?,
-    )
+fn input_path(year: impl std::fmt::Display, name: &str) -> String {
+    format!("inputs/{}/{}.txt", year, name)
 }
 
-fn digit_to_u8(digit: u8) -> Result<u8> {
-    if digit < b'0' || digit > b'9' {
-        return Err(anyhow!("Non numeric digit: {}", digit));
+fn input(year: u16, name: &str) -> Result<String> {
+    let path = input_path(year, name);
+    std::fs::read_to_string(&path).with_context(|| path)
+}
+
+/// The input of a puzzle that is a single number: `given` on the command line, or else the
+/// contents of its input file
+fn number_input<T>(year: u16, name: &str, given: Option<T>) -> Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    if let Some(given) = given {
+        return Ok(given);
     }
 
-    Ok(digit - b'0')
+    let path = input_path(year, name);
+    let contents = std::fs::read_to_string(&path).map_err(|err| {
+        anyhow!(
+            "No input given on the command line and {} can't be read: {}",
+            path,
+            err
+        )
+    })?;
+
+    contents
+        .trim()
+        .parse()
+        .with_context(|| format!("Parsing {}", path))
 }
 
 #[allow(dead_code)]
@@ -57,15 +96,45 @@ fn digits_to_i64(mut digits: &[u8]) -> Result<i64> {
 }
 
 fn main() -> Result<()> {
-    let args = args::Args::parse();
+    let matches = args::Args::into_app().get_matches();
+    let args = args::Args::from_arg_matches(&matches);
 
     FmtSubscriber::builder()
         .with_env_filter(args.env_filter())
         .init();
 
-    let solution = args.command.execute()?;
-
-    println!("Solution:\n{}", solution);
-
-    Ok(())
+    let records = match &args.command {
+        args::Commands::NewDay(new_day) => {
+            println!("{}", new_day.execute()?);
+            return Ok(());
+        }
+        args::Commands::Fetch(fetch) => {
+            println!("{}", fetch.execute()?);
+            return Ok(());
+        }
+        args::Commands::Submit(submit) => {
+            println!("{}", submit.execute()?);
+            return Ok(());
+        }
+        args::Commands::RunAll(run_all) => run_all.records(),
+        args::Commands::Year(solution) => {
+            // `<year> <day> <part>`; the names are only known to the matches
+            let (year, matches) = matches.subcommand().context("Missing year")?;
+            let (day, matches) = matches.subcommand().context("Missing day")?;
+            let (part, _) = matches.subcommand().context("Missing part")?;
+
+            vec![Record::new(year, day, part, || solution.execute())]
+        }
+    };
+
+    println!("{}", args.format.render(&records));
+
+    match records
+        .iter()
+        .filter(|record| record.error.is_some())
+        .count()
+    {
+        0 => Ok(()),
+        failed => Err(anyhow!("{} of {} solutions failed", failed, records.len())),
+    }
 }
@@ -0,0 +1,231 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Clap;
+
+use crate::Command;
+
+const SKELETON: &str = include_str!("../solution.rs.skel");
+
+/// Render `solution.rs.skel` into a new solution module
+#[derive(Debug, Clap)]
+pub struct NewDay {
+    /// Year of the puzzle, e.g. 2017
+    year: u16,
+
+    /// Day of the puzzle, 1 through 25
+    day: u8,
+
+    /// Puzzle title, used for the module doc comment
+    #[clap(long)]
+    title: Option<String>,
+
+    /// Example data from the puzzle description, used for the test constant
+    #[clap(long)]
+    example: Option<String>,
+}
+
+impl Command for NewDay {
+    fn execute(&self) -> Result<String> {
+        if self.day < 1 || self.day > 25 {
+            return Err(anyhow!("Day must be between 1 and 25: {}", self.day));
+        }
+
+        let name = module_name(self.day);
+        let src = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join(format!("y{}", self.year));
+        let module = src.join(format!("{}.rs", name));
+        let rendered = render(
+            SKELETON,
+            self.day,
+            self.title.as_deref().unwrap_or_default(),
+            self.example.as_deref().unwrap_or_default(),
+        );
+
+        std::fs::create_dir_all(&src).with_context(|| format!("Creating {}", src.display()))?;
+        create_new(&module, &rendered)?;
+
+        let year_module = src.join("mod.rs");
+
+        if year_module.exists() {
+            declare(&year_module, &name)?;
+        } else {
+            create_new(&year_module, &new_year_module(self.year, &name))?;
+            declare(
+                &Path::new(env!("CARGO_MANIFEST_DIR")).join("src/main.rs"),
+                &format!("y{}", self.year),
+            )?;
+        }
+
+        let inputs = PathBuf::from(format!("inputs/{}", self.year));
+        std::fs::create_dir_all(&inputs)
+            .with_context(|| format!("Creating {}", inputs.display()))?;
+
+        let input = inputs.join(format!("{}.txt", name));
+
+        if !input.exists() {
+            create_new(&input, "")?;
+        }
+
+        Ok(format!(
+            "Created {}\nInput at {}",
+            module.display(),
+            input.display()
+        ))
+    }
+}
+
+fn module_name(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+/// Create `path` with `contents`, refusing to overwrite an existing file
+fn create_new(path: &Path, contents: &str) -> Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .with_context(|| format!("Creating {}", path.display()))
+}
+
+/// `src/yNNNN/mod.rs` for a year whose first solution is `name`
+fn new_year_module(year: u16, name: &str) -> String {
+    format!(
+        "//! Solutions to the {0} puzzles\n\n\
+         // `new-day` adds a declaration here for every module it creates\n\
+         mod {1};\n\n\
+         // `YEAR`, the input helpers and the `Solutions` registration, generated by `build.rs`\n\
+         include!(concat!(env!(\"OUT_DIR\"), \"/y{0}.rs\"));\n",
+        year, name
+    )
+}
+
+/// Add `mod name;` to the module at `path`, which `build.rs` insists on for every solution
+fn declare(path: &Path, name: &str) -> Result<()> {
+    let source =
+        std::fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+
+    if let Some(declared) = with_declaration(&source, name) {
+        std::fs::write(path, declared).with_context(|| format!("Writing {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// `source` with `mod name;` inserted in order among the declarations sharing its prefix, or
+/// `None` if it's already there
+fn with_declaration(source: &str, name: &str) -> Option<String> {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let declaration = format!("mod {};", name);
+    let mut lines = source.lines().collect::<Vec<_>>();
+
+    let siblings = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let declared = line.strip_prefix("mod ")?.strip_suffix(';')?;
+            declared.starts_with(prefix).then_some((i, declared))
+        })
+        .collect::<Vec<_>>();
+
+    if siblings.iter().any(|&(_, declared)| declared == name) {
+        return None;
+    }
+
+    let at = siblings
+        .iter()
+        .find(|&&(_, declared)| declared > name)
+        .map(|&(i, _)| i)
+        .or_else(|| siblings.last().map(|&(i, _)| i + 1))
+        .unwrap_or(lines.len());
+
+    lines.insert(at, &declaration);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Quote `text` as a raw string literal with enough `#`s to contain it
+fn raw_string_literal(text: &str) -> String {
+    let mut hashes = String::new();
+
+    while text.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+
+    format!("r{0}\"{1}\"{0}", hashes, text)
+}
+
+/// Fill in the skeleton's `{{placeholders}}`
+fn render(template: &str, day: u8, title: &str, example: &str) -> String {
+    template
+        .replace("{{day}}", &module_name(day))
+        .replace("{{number}}", &day.to_string())
+        .replace("{{title}}", title)
+        .replace("{{example}}", &raw_string_literal(example))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let rendered = render(
+            "//! Day {{number}}: {{title}}\ninput(\"{{day}}\")\nconst EXAMPLE: &str = {{example}};",
+            7,
+            "Recursive Circus",
+            "pbga (66)\nxhth (57)",
+        );
+
+        assert_eq!(
+            rendered,
+            "//! Day 7: Recursive Circus\ninput(\"day07\")\nconst EXAMPLE: &str = r\"pbga (66)\nxhth (57)\";"
+        );
+    }
+
+    #[test]
+    fn test_render_skeleton() {
+        let rendered = render(SKELETON, 12, "Digital Plumber", "");
+
+        assert!(rendered.starts_with("//! Day 12: Digital Plumber\n"));
+        assert!(rendered.contains("input(\"day12\")"));
+        assert!(!rendered.contains("{{"));
+    }
+
+    #[test]
+    fn test_with_declaration() {
+        let source = "mod args;\nmod vector;\nmod y2015;\nmod y2017;\n\nfn main() {}\n";
+
+        assert_eq!(
+            with_declaration(source, "y2016").unwrap(),
+            "mod args;\nmod vector;\nmod y2015;\nmod y2016;\nmod y2017;\n\nfn main() {}\n"
+        );
+        assert_eq!(
+            with_declaration(source, "y2018").unwrap(),
+            "mod args;\nmod vector;\nmod y2015;\nmod y2017;\nmod y2018;\n\nfn main() {}\n"
+        );
+        assert_eq!(with_declaration(source, "y2017"), None);
+    }
+
+    #[test]
+    fn test_new_year_module() {
+        let module = new_year_module(2018, "day01");
+
+        assert_eq!(with_declaration(&module, "day01"), None);
+        assert!(with_declaration(&module, "day02")
+            .unwrap()
+            .contains("mod day01;\nmod day02;\n"));
+        assert!(module.ends_with("include!(concat!(env!(\"OUT_DIR\"), \"/y2018.rs\"));\n"));
+    }
+
+    #[test]
+    fn test_raw_string_literal() {
+        assert_eq!(raw_string_literal("abc"), "r\"abc\"");
+        assert_eq!(raw_string_literal("a\"b"), "r#\"a\"b\"#");
+        assert_eq!(raw_string_literal("a\"#b"), "r##\"a\"#b\"##");
+    }
+}
@@ -0,0 +1,232 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Error, Result};
+use serde_json::json;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(anyhow!("Unknown format: {}", s)),
+        }
+    }
+}
+
+/// The outcome of running one part of one day's solution
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub year: String,
+    pub day: String,
+    pub part: String,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    pub input: String,
+    pub error: Option<String>,
+}
+
+impl Record {
+    /// Time `solve` and record its answer or error
+    pub fn new<F>(year: &str, day: &str, part: &str, solve: F) -> Self
+    where
+        F: FnOnce() -> Result<String>,
+    {
+        let start = Instant::now();
+        let result = solve();
+        let elapsed = start.elapsed();
+
+        let (answer, error) = match result {
+            Ok(answer) => (Some(answer), None),
+            Err(e) => (None, Some(format!("{:#}", e))),
+        };
+
+        Self {
+            year: year.to_string(),
+            day: day.to_string(),
+            part: part.to_string(),
+            answer,
+            elapsed,
+            input: crate::input_path(year, day),
+            error,
+        }
+    }
+
+    fn elapsed_ms(&self) -> f64 {
+        self.elapsed.as_secs_f64() * 1000.0
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "answer": self.answer,
+            "elapsed_ms": self.elapsed_ms(),
+            "input": self.input,
+            "error": self.error,
+        })
+    }
+
+    fn to_tsv(&self) -> String {
+        [
+            self.year.as_str(),
+            self.day.as_str(),
+            self.part.as_str(),
+            self.answer.as_deref().unwrap_or_default(),
+            &format!("{:.3}", self.elapsed_ms()),
+            self.input.as_str(),
+            self.error.as_deref().unwrap_or_default(),
+        ]
+        .iter()
+        .map(|field| tsv_escape(field))
+        .collect::<Vec<_>>()
+        .join("\t")
+    }
+}
+
+/// Escape the characters that would break a TSV row
+fn tsv_escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+impl Format {
+    pub fn render(&self, records: &[Record]) -> String {
+        match self {
+            Self::Text => match records {
+                [record] => match (&record.answer, &record.error) {
+                    (Some(answer), _) => format!("Solution:\n{}", answer),
+                    (None, error) => format!("Failed:\n{}", error.as_deref().unwrap_or_default()),
+                },
+                _ => records
+                    .iter()
+                    .map(|record| {
+                        let prefix = format!("{} {} {}", record.year, record.day, record.part);
+
+                        match (&record.answer, &record.error) {
+                            (Some(answer), _) => format!("{}: {}", prefix, answer),
+                            (None, error) => format!(
+                                "{}: error: {}",
+                                prefix,
+                                error.as_deref().unwrap_or_default()
+                            ),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            },
+            Self::Json => match records {
+                [record] => record.to_json().to_string(),
+                _ => serde_json::Value::Array(records.iter().map(Record::to_json).collect())
+                    .to_string(),
+            },
+            Self::Tsv => std::iter::once(String::from(
+                "year\tday\tpart\tanswer\telapsed_ms\tinput\terror",
+            ))
+            .chain(records.iter().map(Record::to_tsv))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: String::from("2017"),
+                day: String::from("day01"),
+                part: String::from("part1"),
+                answer: Some(String::from("1171")),
+                elapsed: Duration::from_micros(1500),
+                input: String::from("inputs/2017/day01.txt"),
+                error: None,
+            },
+            Record {
+                year: String::from("2017"),
+                day: String::from("day02"),
+                part: String::from("part2"),
+                answer: None,
+                elapsed: Duration::from_micros(250),
+                input: String::from("inputs/2017/day02.txt"),
+                error: Some(String::from("inputs/2017/day02.txt: not found\tat all")),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("text".parse::<Format>().unwrap(), Format::Text);
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("tsv".parse::<Format>().unwrap(), Format::Tsv);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_record_new() {
+        let record = Record::new("2017", "day05", "part1", || Ok(String::from("5")));
+        assert_eq!(record.answer.as_deref(), Some("5"));
+        assert_eq!(record.input, "inputs/2017/day05.txt");
+        assert_eq!(record.error, None);
+
+        let record = Record::new("2017", "day05", "part2", || Err(anyhow!("Bad jump")));
+        assert_eq!(record.answer, None);
+        assert_eq!(record.error.as_deref(), Some("Bad jump"));
+    }
+
+    #[test]
+    fn test_render_text() {
+        let records = records();
+
+        assert_eq!(Format::Text.render(&records[..1]), "Solution:\n1171");
+        assert_eq!(
+            Format::Text.render(&records),
+            "2017 day01 part1: 1171\n\
+             2017 day02 part2: error: inputs/2017/day02.txt: not found\tat all"
+        );
+    }
+
+    #[test]
+    fn test_render_json() -> Result<()> {
+        let records = records();
+
+        let single: serde_json::Value = serde_json::from_str(&Format::Json.render(&records[..1]))?;
+        assert_eq!(single["answer"], "1171");
+        assert_eq!(single["elapsed_ms"], 1.5);
+        assert_eq!(single["error"], serde_json::Value::Null);
+
+        let many: serde_json::Value = serde_json::from_str(&Format::Json.render(&records))?;
+        assert_eq!(many[1]["day"], "day02");
+        assert_eq!(many[1]["answer"], serde_json::Value::Null);
+        assert_eq!(many[1]["input"], "inputs/2017/day02.txt");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_tsv() {
+        assert_eq!(
+            Format::Tsv.render(&records()),
+            "year\tday\tpart\tanswer\telapsed_ms\tinput\terror\n\
+             2017\tday01\tpart1\t1171\t1.500\tinputs/2017/day01.txt\t\n\
+             2017\tday02\tpart2\t\t0.250\tinputs/2017/day02.txt\tinputs/2017/day02.txt: not found\\tat all"
+        );
+    }
+}
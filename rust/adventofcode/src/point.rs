@@ -1,146 +1,83 @@
-use std::ops;
+use crate::vector::Vector;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
-pub struct Point {
-    pub x: i64,
-    pub y: i64,
-}
+/// A 2D `i64` position, `y` growing downwards as in the puzzle diagrams
+pub type Point = Vector<i64, 2>;
 
 impl Point {
-    pub fn new(x: i64, y: i64) -> Self {
-        Point { x, y }
-    }
-
     #[allow(dead_code)]
     pub fn as_tuple(&self) -> (i64, i64) {
         (self.x, self.y)
     }
-}
-
-impl ops::Add<Point> for Point {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self::new(self.x + rhs.x, self.y + rhs.y)
-    }
-}
-
-impl ops::Add<i64> for Point {
-    type Output = Point;
-
-    fn add(self, rhs: i64) -> Self {
-        Self::new(self.x + rhs, self.y + rhs)
-    }
-}
-
-impl ops::AddAssign<Point> for Point {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-
-impl ops::AddAssign<i64> for Point {
-    fn add_assign(&mut self, rhs: i64) {
-        self.x += rhs;
-        self.y += rhs;
-    }
-}
-
-impl ops::Sub<Point> for Point {
-    type Output = Self;
 
-    fn sub(self, rhs: Self) -> Self {
-        Self::new(self.x - rhs.x, self.y - rhs.y)
+    /// The orthogonal neighbours: up, right, down, left
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .iter()
+            .map(move |(x, y)| self + Point::new(*x, *y))
     }
-}
 
-impl ops::Sub<i64> for Point {
-    type Output = Point;
-
-    fn sub(self, rhs: i64) -> Self {
-        Self::new(self.x - rhs, self.y - rhs)
+    /// The orthogonal and diagonal neighbours, clockwise from the top left
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+        ]
+        .iter()
+        .map(move |(x, y)| self + Point::new(*x, *y))
     }
-}
 
-impl ops::SubAssign<Point> for Point {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-    }
-}
-
-impl ops::SubAssign<i64> for Point {
-    fn sub_assign(&mut self, rhs: i64) {
-        self.x -= rhs;
-        self.y -= rhs;
-    }
-}
-
-impl ops::Mul<Point> for Point {
-    type Output = Self;
-
-    fn mul(self, rhs: Self) -> Self {
-        Self::new(self.x * rhs.x, self.y * rhs.y)
-    }
-}
-
-impl ops::Mul<i64> for Point {
-    type Output = Self;
-
-    fn mul(self, rhs: i64) -> Self {
-        Self::new(self.x * rhs, self.y * rhs)
-    }
-}
-
-impl ops::MulAssign<Point> for Point {
-    fn mul_assign(&mut self, rhs: Self) {
-        self.x *= rhs.x;
-        self.y *= rhs.y;
+    /// A quarter turn clockwise about the origin, as drawn with `y` growing downwards
+    #[allow(dead_code)]
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y, self.x)
     }
-}
 
-impl ops::MulAssign<i64> for Point {
-    fn mul_assign(&mut self, rhs: i64) {
-        self.x *= rhs;
-        self.y *= rhs;
+    /// A quarter turn counter-clockwise about the origin, as drawn with `y` growing downwards
+    #[allow(dead_code)]
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y, -self.x)
     }
-}
-
-impl ops::Div<Point> for Point {
-    type Output = Self;
 
-    fn div(self, rhs: Self) -> Self {
-        Self::new(self.x / rhs.x, self.y / rhs.y)
+    #[allow(dead_code)]
+    pub fn rotate_right_about(&self, center: Point) -> Self {
+        (*self - center).rotate_right() + center
     }
-}
 
-impl ops::Div<i64> for Point {
-    type Output = Self;
-
-    fn div(self, rhs: i64) -> Self {
-        Self::new(self.x / rhs, self.y / rhs)
+    #[allow(dead_code)]
+    pub fn rotate_left_about(&self, center: Point) -> Self {
+        (*self - center).rotate_left() + center
     }
-}
 
-impl ops::DivAssign<Point> for Point {
-    fn div_assign(&mut self, rhs: Self) {
-        self.x /= rhs.x;
-        self.y /= rhs.y;
+    /// Mirror across the x axis
+    #[allow(dead_code)]
+    pub fn reflect_x(&self) -> Self {
+        Self::new(self.x, -self.y)
     }
-}
 
-impl ops::DivAssign<i64> for Point {
-    fn div_assign(&mut self, rhs: i64) {
-        self.x /= rhs;
-        self.y /= rhs;
+    /// Mirror across the y axis
+    #[allow(dead_code)]
+    pub fn reflect_y(&self) -> Self {
+        Self::new(-self.x, self.y)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use anyhow::Result;
+    use proptest::prelude::*;
+
     use super::*;
 
+    fn point() -> impl Strategy<Value = Point> {
+        (-1_000_000i64..1_000_000, -1_000_000i64..1_000_000).prop_map(|(x, y)| Point::new(x, y))
+    }
+
     #[test]
     fn test_point_create() {
         assert_eq!(Point::new(1, 2).as_tuple(), (1, 2));
@@ -202,4 +139,107 @@ mod test {
         p /= 2;
         assert_eq!(p, Point::new(5, 3));
     }
+
+    #[test]
+    fn test_point_distance() {
+        assert_eq!(Point::new(0, 0).manhattan(Point::new(2, -1)), 3);
+        assert_eq!(Point::new(3, 4).manhattan(Point::new(-1, 1)), 7);
+        assert_eq!(Point::new(0, 0).chebyshev(Point::new(2, -1)), 2);
+        assert_eq!(Point::new(3, 4).chebyshev(Point::new(-1, 1)), 4);
+    }
+
+    #[test]
+    fn test_point_neighbors() {
+        assert_eq!(
+            Point::new(1, 1).neighbors4().collect::<Vec<_>>(),
+            vec![
+                Point::new(1, 0),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(0, 1)
+            ]
+        );
+        assert_eq!(Point::new(1, 1).neighbors8().count(), 8);
+        assert_eq!(
+            Point::default().neighbors8().next(),
+            Some(Point::new(-1, -1))
+        );
+    }
+
+    #[test]
+    fn test_point_rotate() {
+        assert_eq!(Point::new(1, 0).rotate_right(), Point::new(0, 1));
+        assert_eq!(Point::new(0, 1).rotate_right(), Point::new(-1, 0));
+        assert_eq!(Point::new(1, 0).rotate_left(), Point::new(0, -1));
+        assert_eq!(
+            Point::new(3, 1).rotate_right_about(Point::new(2, 1)),
+            Point::new(2, 2)
+        );
+        assert_eq!(
+            Point::new(3, 1).rotate_left_about(Point::new(2, 1)),
+            Point::new(2, 0)
+        );
+    }
+
+    #[test]
+    fn test_point_reflect() {
+        assert_eq!(Point::new(3, 4).reflect_x(), Point::new(3, -4));
+        assert_eq!(Point::new(3, 4).reflect_y(), Point::new(-3, 4));
+        assert_eq!(Point::new(-3, 0).signum(), Point::new(-1, 0));
+        assert_eq!(Point::new(-3, 7).abs(), Point::new(3, 7));
+    }
+
+    #[test]
+    fn test_point_parse() -> Result<()> {
+        assert_eq!("3,-4".parse::<Point>()?, Point::new(3, -4));
+        assert_eq!(" 3, 4 ".parse::<Point>()?, Point::new(3, 4));
+        assert_eq!(Point::new(-1, 2).to_string(), "-1,2");
+        assert!("3".parse::<Point>().is_err());
+        assert!("3,x".parse::<Point>().is_err());
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_distances(a in point(), b in point(), c in point()) {
+            prop_assert_eq!(a.manhattan(b), b.manhattan(a));
+            prop_assert_eq!(a.manhattan(b), (a - b).manhattan(Point::default()));
+            prop_assert!(a.manhattan(c) <= a.manhattan(b) + b.manhattan(c));
+            prop_assert!(a.chebyshev(c) <= a.chebyshev(b) + b.chebyshev(c));
+            prop_assert!(a.chebyshev(b) <= a.manhattan(b));
+            prop_assert!(a.manhattan(b) <= 2 * a.chebyshev(b));
+        }
+
+        #[test]
+        fn prop_rotations(p in point(), center in point()) {
+            prop_assert_eq!(p.rotate_right().rotate_right().rotate_right().rotate_right(), p);
+            prop_assert_eq!(p.rotate_right().rotate_left(), p);
+            prop_assert_eq!(p.rotate_right().rotate_right(), p * -1);
+            prop_assert_eq!(p.rotate_left(), p.rotate_right() * -1);
+            prop_assert_eq!(p.rotate_right().manhattan(Point::default()), p.manhattan(Point::default()));
+            prop_assert_eq!(p.rotate_right_about(center).manhattan(center), p.manhattan(center));
+            prop_assert_eq!(p.rotate_left_about(center).rotate_right_about(center), p);
+        }
+
+        #[test]
+        fn prop_reflections(p in point()) {
+            prop_assert_eq!(p.reflect_x().reflect_x(), p);
+            prop_assert_eq!(p.reflect_y().reflect_y(), p);
+            prop_assert_eq!(p.reflect_x().reflect_y(), p * -1);
+            prop_assert_eq!(p.signum() * p.abs(), p);
+        }
+
+        #[test]
+        fn prop_neighbors(p in point()) {
+            prop_assert!(p.neighbors4().all(|n| n.manhattan(p) == 1));
+            prop_assert!(p.neighbors8().all(|n| n.chebyshev(p) == 1));
+            prop_assert!(p.neighbors4().all(|n| p.neighbors8().any(|m| m == n)));
+        }
+
+        #[test]
+        fn prop_parse_round_trip(p in point()) {
+            prop_assert_eq!(p.to_string().parse::<Point>().unwrap(), p);
+        }
+    }
 }
@@ -0,0 +1,203 @@
+use std::fmt;
+
+use crate::Point;
+
+/// An axis-aligned rectangle of points, inclusive of both corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    min: Point,
+    max: Point,
+}
+
+impl Rect {
+    /// The rectangle with `a` and `b` as opposite corners, in either order
+    pub fn new(a: Point, b: Point) -> Self {
+        Self {
+            min: a.zip_with(b, i64::min),
+            max: a.zip_with(b, i64::max),
+        }
+    }
+
+    /// The smallest rectangle containing every point, or `None` if there aren't any
+    pub fn bounding<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Self::new(first, first), |rect, point| {
+            rect.union(Self::new(point, point))
+        }))
+    }
+
+    /// The top left corner
+    pub fn min(&self) -> Point {
+        self.min
+    }
+
+    /// The bottom right corner
+    pub fn max(&self) -> Point {
+        self.max
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    /// The number of points in the rectangle
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The points in both rectangles, or `None` if they don't overlap
+    pub fn intersection(&self, other: Self) -> Option<Self> {
+        let min = self.min.zip_with(other.min, i64::max);
+        let max = self.max.zip_with(other.max, i64::min);
+
+        if min.x > max.x || min.y > max.y {
+            return None;
+        }
+
+        Some(Self { min, max })
+    }
+
+    /// The smallest rectangle containing both rectangles
+    pub fn union(&self, other: Self) -> Self {
+        Self {
+            min: self.min.zip_with(other.min, i64::min),
+            max: self.max.zip_with(other.max, i64::max),
+        }
+    }
+
+    /// Every point in row-major order, matching `Point`'s `Ord`
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Self { min, max } = *self;
+
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+
+    /// The points of each row, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = Point>> {
+        let Self { min, max } = *self;
+
+        (min.y..=max.y).map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+impl fmt::Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn point() -> impl Strategy<Value = Point> {
+        (-20i64..20, -20i64..20).prop_map(|(x, y)| Point::new(x, y))
+    }
+
+    fn rect() -> impl Strategy<Value = Rect> {
+        (point(), point()).prop_map(|(a, b)| Rect::new(a, b))
+    }
+
+    #[test]
+    fn test_rect_new() {
+        let rect = Rect::new(Point::new(3, -1), Point::new(-2, 4));
+
+        assert_eq!(rect.min(), Point::new(-2, -1));
+        assert_eq!(rect.max(), Point::new(3, 4));
+        assert_eq!(rect.width(), 6);
+        assert_eq!(rect.height(), 6);
+        assert_eq!(rect.area(), 36);
+        assert_eq!(rect.to_string(), "-2,-1..=3,4");
+    }
+
+    #[test]
+    fn test_rect_bounding() {
+        assert_eq!(Rect::bounding(vec![]), None);
+        assert_eq!(
+            Rect::bounding(vec![Point::new(1, 1)]),
+            Some(Rect::new(Point::new(1, 1), Point::new(1, 1)))
+        );
+        assert_eq!(
+            Rect::bounding(vec![Point::new(1, 5), Point::new(-3, 2), Point::new(0, 7)]),
+            Some(Rect::new(Point::new(-3, 2), Point::new(1, 7)))
+        );
+    }
+
+    #[test]
+    fn test_rect_contains() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(2, 1));
+
+        assert!(rect.contains(Point::new(0, 0)));
+        assert!(rect.contains(Point::new(2, 1)));
+        assert!(!rect.contains(Point::new(3, 1)));
+        assert!(!rect.contains(Point::new(1, -1)));
+    }
+
+    #[test]
+    fn test_rect_intersection() {
+        let a = Rect::new(Point::new(0, 0), Point::new(4, 4));
+        let b = Rect::new(Point::new(3, 2), Point::new(6, 8));
+        let c = Rect::new(Point::new(5, 5), Point::new(6, 6));
+
+        assert_eq!(
+            a.intersection(b),
+            Some(Rect::new(Point::new(3, 2), Point::new(4, 4)))
+        );
+        assert_eq!(a.intersection(c), None);
+        assert_eq!(a.union(c), Rect::new(Point::new(0, 0), Point::new(6, 6)));
+    }
+
+    #[test]
+    fn test_rect_points() {
+        let rect = Rect::new(Point::new(1, 0), Point::new(2, 1));
+
+        assert_eq!(
+            rect.points().collect::<Vec<_>>(),
+            vec![
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(1, 1),
+                Point::new(2, 1)
+            ]
+        );
+        assert_eq!(rect.rows().count(), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_points(rect in rect()) {
+            let points = rect.points().collect::<Vec<_>>();
+            let mut sorted = points.clone();
+            sorted.sort();
+
+            prop_assert_eq!(points.len() as i64, rect.area());
+            prop_assert_eq!(&points, &sorted);
+            prop_assert!(points.iter().all(|p| rect.contains(*p)));
+            prop_assert_eq!(Rect::bounding(points), Some(rect));
+        }
+
+        #[test]
+        fn prop_intersection(a in rect(), b in rect(), p in point()) {
+            let both = a.contains(p) && b.contains(p);
+
+            prop_assert_eq!(a.intersection(b).is_some_and(|r| r.contains(p)), both);
+            prop_assert!(!(a.contains(p) || b.contains(p)) || a.union(b).contains(p));
+            prop_assert_eq!(a.union(b), b.union(a));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! A tiny reproducible random number generator for benchmarks and tests
+
+/// Marsaglia's 64 bit xorshift, which is fast and repeatable but not much else
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// The seed used when any will do
+    pub const SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+    /// A generator starting from `seed`, which can't be zero as that never changes
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "A xorshift seed can't be zero");
+
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A value in `0..bound`, with a slight bias towards small values unless `bound` is a
+    /// power of two
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_xorshift() {
+        let mut rng = XorShift::new(1);
+
+        assert_eq!(rng.next_u64(), 1082269761);
+        assert_eq!(rng.next_u64(), 1152992998833853505);
+        assert_eq!(rng.next_u64(), 11177516664432764457);
+
+        let mut rng = XorShift::new(XorShift::SEED);
+        assert!((0..1000).all(|_| rng.below(7) < 7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_xorshift_zero_seed() {
+        XorShift::new(0);
+    }
+}
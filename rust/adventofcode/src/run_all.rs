@@ -0,0 +1,43 @@
+use anyhow::Result;
+use clap::Clap;
+
+use crate::output::Record;
+use crate::{Command, YEARS};
+
+pub const PARTS: &[&str] = &["part1", "part2"];
+
+/// Run both parts of every registered solution, optionally limited to one year
+#[derive(Debug, Clap)]
+pub struct RunAll {
+    /// Only run solutions for this year
+    #[clap(long)]
+    year: Option<u16>,
+}
+
+/// Parse and run `adventofcode <year> <day> <part>`
+pub fn run(year: &str, day: &str, part: &str) -> Result<String> {
+    match crate::args::Args::try_parse_from(["adventofcode", year, day, part])?.command {
+        crate::args::Commands::Year(solution) => solution.execute(),
+        command => unreachable!("{:?} is not a solution", command),
+    }
+}
+
+impl RunAll {
+    pub fn records(&self) -> Vec<Record> {
+        let mut records = Vec::new();
+
+        for (year, days) in YEARS {
+            if self.year.is_some_and(|only| only.to_string() != *year) {
+                continue;
+            }
+
+            for day in days.iter() {
+                for part in PARTS {
+                    records.push(Record::new(year, day, part, || run(year, day, part)));
+                }
+            }
+        }
+
+        records
+    }
+}
@@ -0,0 +1,566 @@
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Error, Result};
+use clap::Clap;
+use tracing::debug;
+
+use crate::client::{Client, ClientArgs};
+use crate::{Command, YEARS};
+
+/// Run a solution and submit its answer, unless the answer log already rules it out
+#[derive(Debug, Clap)]
+pub struct Submit {
+    /// Day of the puzzle, 1 through 25
+    day: u8,
+
+    /// Part of the puzzle, 1 or 2
+    part: u8,
+
+    /// Year of the puzzle, defaults to the latest registered year
+    #[clap(long)]
+    year: Option<u16>,
+
+    #[clap(flatten)]
+    client: ClientArgs,
+}
+
+impl Command for Submit {
+    fn execute(&self) -> Result<String> {
+        if !(1..=25).contains(&self.day) || !(1..=2).contains(&self.part) {
+            return Err(anyhow!(
+                "No such puzzle: day {} part {}",
+                self.day,
+                self.part
+            ));
+        }
+
+        let year = match self.year {
+            Some(year) => year.to_string(),
+            None => YEARS
+                .last()
+                .map(|(year, _)| year.to_string())
+                .ok_or_else(|| anyhow!("No years registered"))?,
+        };
+
+        let day = format!("day{:02}", self.day);
+        let answer = crate::run_all::run(&year, &day, &format!("part{}", self.part))?;
+
+        let mut log = AnswerLog::load(&PathBuf::from(format!("answers/{}/{}.tsv", year, day)))?;
+        let outcome = submit(
+            &self.client.client()?,
+            &mut log,
+            year.parse()?,
+            self.day,
+            self.part,
+            answer.trim(),
+            SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        )?;
+
+        Ok(format!("{}: {}", answer.trim(), outcome.describe()))
+    }
+}
+
+/// What the site said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too recently; try again after this many seconds
+    Wait(u64),
+    /// The part was already solved, or is not unlocked yet
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    /// Classify the article text of an answer response page
+    pub fn parse(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Self::Right
+        } else if page.contains("You gave an answer too recently") {
+            Self::Wait(parse_wait(page).unwrap_or(60))
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Self::TooHigh
+            } else if page.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if page.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether this outcome rules the answer out for good
+    fn is_wrong(&self) -> bool {
+        matches!(self, Self::Wrong | Self::TooHigh | Self::TooLow)
+    }
+
+    /// Seconds the site refuses further answers for the day after this outcome
+    ///
+    /// A wrong answer locks the day for at least a minute. The site lengthens that after
+    /// repeated wrong answers, which shows up as a `Wait` if we come back too early.
+    fn lockout(&self) -> Option<u64> {
+        match self {
+            Self::Wait(seconds) => Some(*seconds),
+            _ if self.is_wrong() => Some(60),
+            _ => None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Right => String::from("right"),
+            Self::Wrong => String::from("wrong"),
+            Self::TooHigh => String::from("wrong, too high"),
+            Self::TooLow => String::from("wrong, too low"),
+            Self::Wait(seconds) => format!("not checked, wait {} seconds", seconds),
+            Self::WrongLevel => String::from("not checked, already solved or locked"),
+            Self::Unknown => String::from("unrecognized response"),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Right => write!(f, "right"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::TooHigh => write!(f, "too-high"),
+            Self::TooLow => write!(f, "too-low"),
+            Self::Wait(seconds) => write!(f, "wait-{}", seconds),
+            Self::WrongLevel => write!(f, "wrong-level"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "right" => Ok(Self::Right),
+            "wrong" => Ok(Self::Wrong),
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            "wrong-level" => Ok(Self::WrongLevel),
+            "unknown" => Ok(Self::Unknown),
+            _ => s
+                .strip_prefix("wait-")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Self::Wait)
+                .ok_or_else(|| anyhow!("Unknown outcome: {}", s)),
+        }
+    }
+}
+
+/// Seconds from "You have 1m 5s left to wait"
+fn parse_wait(page: &str) -> Option<u64> {
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("ave ")? + "ave ".len();
+
+    page[start..end]
+        .split_whitespace()
+        .map(|part| {
+            // The page isn't ours, so don't assume the unit is a single byte
+            let (unit_start, _) = part.char_indices().last()?;
+            let (number, unit) = part.split_at(unit_start);
+            let number = number.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// One line of the answer log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.timestamp, self.part, self.answer, self.outcome
+        )
+    }
+}
+
+impl FromStr for Attempt {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split('\t').collect::<Vec<_>>()[..] {
+            [timestamp, part, answer, outcome] => Ok(Self {
+                timestamp: timestamp.parse()?,
+                part: part.parse()?,
+                answer: answer.to_string(),
+                outcome: outcome.parse()?,
+            }),
+            _ => Err(anyhow!("Bad answer log line: {}", s)),
+        }
+    }
+}
+
+/// Every answer submitted for one day, kept as a TSV file
+pub struct AnswerLog {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl AnswerLog {
+    pub fn load(path: &Path) -> Result<Self> {
+        let attempts = match std::fs::read_to_string(path) {
+            Ok(contents) => contents
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<_>>>()
+                .with_context(|| path.display().to_string())?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(Error::from(e).context(path.display().to_string())),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    /// Refuse answers the log already knows the outcome of, or any answer while the day is
+    /// still locked after the last one, as of `now` in seconds since the epoch
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<()> {
+        let value = answer.parse::<i64>().ok();
+
+        // The lockout covers both parts of the day
+        let latest = self.attempts.last();
+
+        if let Some((timestamp, seconds)) =
+            latest.and_then(|attempt| Some((attempt.timestamp, attempt.outcome.lockout()?)))
+        {
+            let until = timestamp.saturating_add(seconds);
+
+            if until > now {
+                return Err(anyhow!(
+                    "Submitted too recently, wait another {} seconds",
+                    until - now
+                ));
+            }
+        }
+
+        for attempt in self.attempts.iter().filter(|attempt| attempt.part == part) {
+            if attempt.outcome == Outcome::Right {
+                return Err(anyhow!(
+                    "Part {} was already solved with {}",
+                    part,
+                    attempt.answer
+                ));
+            }
+
+            if attempt.answer == answer && attempt.outcome.is_wrong() {
+                return Err(anyhow!(
+                    "{} is already known to be {}",
+                    answer,
+                    attempt.outcome
+                ));
+            }
+
+            let bound = attempt.answer.parse::<i64>().ok();
+
+            match (value, bound, attempt.outcome) {
+                (Some(value), Some(bound), Outcome::TooHigh) if value >= bound => {
+                    return Err(anyhow!("{} is not below known too-high {}", value, bound))
+                }
+                (Some(value), Some(bound), Outcome::TooLow) if value <= bound => {
+                    return Err(anyhow!("{} is not above known too-low {}", value, bound))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", attempt))
+            .with_context(|| format!("Writing {}", self.path.display()))?;
+
+        self.attempts.push(attempt);
+
+        Ok(())
+    }
+}
+
+/// Whether `answer` looks like something the site would accept: a number, a word, or a list
+/// of them run together with commas, rather than a value still wrapped in debug formatting
+fn is_plain_answer(answer: &str) -> bool {
+    !answer.is_empty()
+        && answer
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == ',')
+}
+
+/// Post `answer` unless `log` rules it out, and record what the site said as of `now`
+fn submit(
+    client: &Client,
+    log: &mut AnswerLog,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+    now: u64,
+) -> Result<Outcome> {
+    if !is_plain_answer(answer) {
+        return Err(anyhow!("Refusing to submit answer {:?}", answer));
+    }
+
+    log.check(part, answer, now)?;
+
+    let page = client.post_form(
+        &format!("/{}/day/{}/answer", year, day),
+        &[("level", &part.to_string()), ("answer", answer)],
+    )?;
+
+    let outcome = Outcome::parse(&page);
+    debug!(%outcome, "Submitted");
+
+    log.record(Attempt {
+        timestamp: now,
+        part,
+        answer: answer.to_string(),
+        outcome,
+    })?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::mock;
+
+    const RIGHT: &str = "<article><p>That's the right answer! You are one gold star closer to \
+                         fixing the printer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+                            Please wait one minute before trying again.</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low. \
+                           Please wait one minute before trying again.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer. If you're stuck, make sure \
+                         you're using the full input data.</p></article>";
+    const WAIT: &str = "<article><p>You gave an answer too recently; you have to wait after \
+                        submitting an answer before trying again.  You have 1m 5s left to \
+                        wait.</p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did \
+                               you already complete it?</p></article>";
+
+    /// When every attempt in these tests was made
+    const NOW: u64 = 1_512_086_400;
+
+    fn attempt(part: u8, answer: &str, outcome: Outcome) -> Attempt {
+        Attempt {
+            timestamp: NOW,
+            part,
+            answer: answer.to_string(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_outcome_parse() {
+        assert_eq!(Outcome::parse(RIGHT), Outcome::Right);
+        assert_eq!(Outcome::parse(TOO_HIGH), Outcome::TooHigh);
+        assert_eq!(Outcome::parse(TOO_LOW), Outcome::TooLow);
+        assert_eq!(Outcome::parse(WRONG), Outcome::Wrong);
+        assert_eq!(Outcome::parse(WAIT), Outcome::Wait(65));
+        assert_eq!(Outcome::parse(WRONG_LEVEL), Outcome::WrongLevel);
+        assert_eq!(Outcome::parse("<html></html>"), Outcome::Unknown);
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("You have 45s left to wait."), Some(45));
+        assert_eq!(parse_wait("you have 4m 0s left to wait."), Some(240));
+        assert_eq!(parse_wait("You have 1h 2m 3s left to wait."), Some(3723));
+        assert_eq!(parse_wait("Please wait one minute."), None);
+        assert_eq!(parse_wait("You have 5é left to wait."), None);
+        assert_eq!(parse_wait("You have é left to wait."), None);
+    }
+
+    #[test]
+    fn test_is_plain_answer() {
+        assert!(is_plain_answer("295229"));
+        assert!(is_plain_answer("-12"));
+        assert!(is_plain_answer("uownj"));
+        assert!(is_plain_answer("33,45"));
+        assert!(!is_plain_answer(""));
+        assert!(!is_plain_answer("Some(295229)"));
+        assert!(!is_plain_answer("1 2"));
+        assert!(!is_plain_answer("\"abc\""));
+    }
+
+    #[test]
+    fn test_attempt_round_trip() -> Result<()> {
+        for outcome in &[
+            Outcome::Right,
+            Outcome::Wrong,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Wait(30),
+            Outcome::WrongLevel,
+            Outcome::Unknown,
+        ] {
+            let attempt = attempt(2, "1024", *outcome);
+            assert_eq!(attempt.to_string().parse::<Attempt>()?, attempt);
+        }
+
+        assert!("1\t2\t3".parse::<Attempt>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_answer_log_check() -> Result<()> {
+        let dir = mock::scratch_dir("submit-check");
+        let mut log = AnswerLog::load(&dir.join("day01.tsv"))?;
+
+        log.record(attempt(1, "500", Outcome::TooHigh))?;
+        log.record(attempt(1, "100", Outcome::TooLow))?;
+        log.record(attempt(1, "250", Outcome::Wrong))?;
+        log.record(attempt(1, "260", Outcome::Wait(90)))?;
+
+        // Checked once the wait after the last attempt is over
+        assert!(log.check(1, "250", NOW + 90).is_err());
+        assert!(log.check(1, "500", NOW + 90).is_err());
+        assert!(log.check(1, "600", NOW + 90).is_err());
+        assert!(log.check(1, "100", NOW + 90).is_err());
+        assert!(log.check(1, "50", NOW + 90).is_err());
+        assert!(log.check(1, "260", NOW + 90).is_ok());
+        assert!(log.check(1, "300", NOW + 90).is_ok());
+        assert!(log.check(2, "600", NOW + 90).is_ok());
+
+        let reloaded = AnswerLog::load(&dir.join("day01.tsv"))?;
+        assert_eq!(reloaded.attempts, log.attempts);
+
+        log.record(attempt(2, "abc", Outcome::Right))?;
+        assert!(log.check(2, "def", NOW + 90).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_answer_log_wait() -> Result<()> {
+        let dir = mock::scratch_dir("submit-wait");
+        let mut log = AnswerLog::load(&dir.join("day01.tsv"))?;
+
+        log.record(attempt(1, "300", Outcome::Wait(65)))?;
+
+        let err = log.check(1, "260", NOW + 5).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Submitted too recently, wait another 60 seconds"
+        );
+        assert!(log.check(1, "260", NOW + 65).is_ok());
+        assert!(log.check(2, "260", NOW + 5).is_err());
+
+        // Only the latest attempt counts, as a later one means the wait was over
+        log.record(attempt(1, "310", Outcome::Right))?;
+        assert!(log.check(2, "260", NOW + 5).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_answer_log_wrong_lockout() -> Result<()> {
+        let dir = mock::scratch_dir("submit-lockout");
+        let mut log = AnswerLog::load(&dir.join("day01.tsv"))?;
+
+        for outcome in &[Outcome::Wrong, Outcome::TooHigh, Outcome::TooLow] {
+            log.record(attempt(2, "300", *outcome))?;
+
+            let err = log.check(1, "260", NOW + 15).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Submitted too recently, wait another 45 seconds"
+            );
+            assert!(log.check(2, "200", NOW + 59).is_err());
+            assert!(log.check(1, "260", NOW + 60).is_ok());
+        }
+
+        log.record(attempt(2, "200", Outcome::WrongLevel))?;
+        assert!(log.check(1, "260", NOW).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit() -> Result<()> {
+        let dir = mock::scratch_dir("submit");
+        let (base_url, server) =
+            mock::serve(vec![(200, TOO_HIGH.to_string()), (200, RIGHT.to_string())]);
+        let client = Client::new(
+            &base_url,
+            "abc123",
+            Duration::from_secs(0),
+            &dir.join("last"),
+        );
+        let mut log = AnswerLog::load(&dir.join("day05.tsv"))?;
+
+        assert_eq!(
+            submit(&client, &mut log, 2017, 5, 2, "30000000", NOW)?,
+            Outcome::TooHigh
+        );
+        assert!(submit(&client, &mut log, 2017, 5, 2, "28178177", NOW + 30).is_err());
+        assert!(submit(&client, &mut log, 2017, 5, 2, "30000001", NOW + 60).is_err());
+        assert_eq!(
+            submit(&client, &mut log, 2017, 5, 2, "28178177", NOW + 60)?,
+            Outcome::Right
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2017/day/5/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=30000000"));
+        assert!(requests[1].ends_with("\r\n\r\nlevel=2&answer=28178177"));
+
+        let log = std::fs::read_to_string(dir.join("day05.tsv"))?;
+        assert_eq!(log.lines().count(), 2);
+        assert!(log
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with("\t2\t30000000\ttoo-high"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,651 @@
+use std::convert::TryInto;
+use std::fmt;
+use std::ops;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+/// An `N` dimensional vector of `T`s, with component-wise arithmetic
+///
+/// The `ops` impls inherit `T`'s overflow and division by zero behaviour; use the `checked_*`
+/// methods where that matters.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector<T, const N: usize>(pub [T; N]);
+
+/// Named access to the components of 2D vectors
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Xy<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Named access to the components of 3D vectors
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Xyz<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Named access to the components of 4D vectors
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Xyzw<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+macro_rules! named_components {
+    ($n:literal, $named:ident, $($field:ident),+) => {
+        impl<T> Vector<T, $n> {
+            pub fn new($($field: T),+) -> Self {
+                Self([$($field),+])
+            }
+
+            /// Fails to compile, wherever `Deref` is used for a `T`, if the named struct
+            /// doesn't lay out exactly like the array
+            const SAME_LAYOUT: () = assert!(
+                std::mem::size_of::<$named<T>>() == std::mem::size_of::<[T; $n]>()
+                    && std::mem::align_of::<$named<T>>() == std::mem::align_of::<[T; $n]>()
+            );
+        }
+
+        impl<T> ops::Deref for Vector<T, $n> {
+            type Target = $named<T>;
+
+            fn deref(&self) -> &$named<T> {
+                let () = Self::SAME_LAYOUT;
+
+                // SAFETY: `Vector` is a transparent `[T; N]`, and a `#[repr(C)]` struct of `N`
+                // `T` fields puts them at the same offsets with no padding, as `SAME_LAYOUT`
+                // checks. The borrow of `self` covers the whole struct.
+                unsafe { &*(self.0.as_ptr() as *const $named<T>) }
+            }
+        }
+
+        impl<T> ops::DerefMut for Vector<T, $n> {
+            fn deref_mut(&mut self) -> &mut $named<T> {
+                let () = Self::SAME_LAYOUT;
+
+                // SAFETY: As for `deref`, and the exclusive borrow of `self` makes this the only
+                // reference to the components.
+                unsafe { &mut *(self.0.as_mut_ptr() as *mut $named<T>) }
+            }
+        }
+    };
+}
+
+named_components!(2, Xy, x, y);
+named_components!(3, Xyz, x, y, z);
+named_components!(4, Xyzw, x, y, z, w);
+
+impl<T: Copy, const N: usize> Vector<T, N> {
+    /// A vector with every component set to `value`
+    pub fn splat(value: T) -> Self {
+        Self([value; N])
+    }
+
+    pub fn as_array(&self) -> &[T; N] {
+        &self.0
+    }
+
+    /// Apply `f` to each component
+    pub fn map<U, F>(self, f: F) -> Vector<U, N>
+    where
+        F: FnMut(T) -> U,
+    {
+        Vector(self.0.map(f))
+    }
+
+    /// Combine the matching components of `self` and `other` with `f`
+    pub fn zip_with<F>(self, other: Self, mut f: F) -> Self
+    where
+        F: FnMut(T, T) -> T,
+    {
+        let mut result = self;
+
+        for (l, r) in result.0.iter_mut().zip(other.0.iter()) {
+            *l = f(*l, *r);
+        }
+
+        result
+    }
+}
+
+impl<const N: usize> Vector<i64, N> {
+    /// Taxicab distance
+    pub fn manhattan(&self, other: Self) -> i64 {
+        (*self - other).0.iter().map(|c| c.abs()).sum()
+    }
+
+    /// King's move distance
+    pub fn chebyshev(&self, other: Self) -> i64 {
+        (*self - other).0.iter().map(|c| c.abs()).max().unwrap_or(0)
+    }
+
+    pub fn abs(&self) -> Self {
+        self.map(i64::abs)
+    }
+
+    /// Each component reduced to -1, 0 or 1, e.g. the unit step towards a point
+    pub fn signum(&self) -> Self {
+        self.map(i64::signum)
+    }
+}
+
+/// The overflow-aware integer operations `Vector` forwards to each component
+pub trait Integer: Copy {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem_euclid(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),+) => {
+        $(
+            impl Integer for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn checked_rem_euclid(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem_euclid(self, rhs)
+                }
+            }
+        )+
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: Integer, const N: usize> Vector<T, N> {
+    /// Combine the matching components with `f`, or `None` if any of them fails
+    fn try_zip_with<F>(self, other: Self, f: F) -> Option<Self>
+    where
+        F: Fn(T, T) -> Option<T>,
+    {
+        let mut result = self;
+
+        for (l, r) in result.0.iter_mut().zip(other.0.iter()) {
+            *l = f(*l, *r)?;
+        }
+
+        Some(result)
+    }
+
+    /// Component-wise addition, or `None` on overflow
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.try_zip_with(rhs, T::checked_add)
+    }
+
+    /// Component-wise subtraction, or `None` on overflow
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.try_zip_with(rhs, T::checked_sub)
+    }
+
+    /// Component-wise multiplication, or `None` on overflow
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.try_zip_with(rhs, T::checked_mul)
+    }
+
+    /// Component-wise division, or `None` on overflow or a zero divisor component
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.try_zip_with(rhs, T::checked_div)
+    }
+
+    /// Component-wise non-negative remainder, or `None` on a zero divisor component
+    pub fn checked_rem_euclid(self, rhs: Self) -> Option<Self> {
+        self.try_zip_with(rhs, T::checked_rem_euclid)
+    }
+
+    /// Component-wise non-negative remainder, panicking on a zero divisor component
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        self.checked_rem_euclid(rhs)
+            .expect("rem_euclid by a zero or overflowing component")
+    }
+}
+
+/// A board whose edges wrap around, so leaving one side re-enters on the opposite side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Torus<T, const N: usize> {
+    size: Vector<T, N>,
+}
+
+impl<T: Integer + Default + PartialOrd, const N: usize> Torus<T, N> {
+    /// A torus `size` wide along each axis; every component must be positive
+    pub fn new(size: Vector<T, N>) -> Result<Self> {
+        if size.0.iter().any(|c| *c <= T::default()) {
+            return Err(anyhow!("Torus dimensions must be positive"));
+        }
+
+        Ok(Self { size })
+    }
+
+    pub fn size(&self) -> Vector<T, N> {
+        self.size
+    }
+
+    /// The equivalent position within `0..size` on every axis
+    pub fn wrap(&self, pos: Vector<T, N>) -> Vector<T, N> {
+        pos.rem_euclid(self.size)
+    }
+
+    /// Move `pos` by `delta`, wrapping around the edges without overflowing
+    pub fn offset(&self, pos: Vector<T, N>, delta: Vector<T, N>) -> Vector<T, N> {
+        let pos = self.wrap(pos);
+        let delta = self.wrap(delta);
+
+        // Both halves are below `size` now, so only the sum can overflow; step back first
+        let room = self.size.checked_sub(delta).unwrap();
+        let mut result = pos;
+
+        for i in 0..N {
+            result.0[i] = if pos.0[i] >= room.0[i] {
+                pos.0[i].checked_sub(room.0[i]).unwrap()
+            } else {
+                pos.0[i].checked_add(delta.0[i]).unwrap()
+            };
+        }
+
+        result
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Self::splat(T::default())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(components: [T; N]) -> Self {
+        Self(components)
+    }
+}
+
+/// Row-major order: the last component is the most significant, so 2D points sort by `y`
+/// and then `x`, the way they're read off a map
+impl<T: Ord, const N: usize> Ord for Vector<T, N> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<T: Ord, const N: usize> PartialOrd for Vector<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, const N: usize> ops::Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> ops::IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Display for Vector<T, N> {
+    /// Comma separated components, e.g. `1,-2,3`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, component) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+
+            write!(f, "{}", component)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> FromStr for Vector<T, N>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = Error;
+
+    /// Parse comma separated components, e.g. `1, -2, 3`
+    fn from_str(s: &str) -> Result<Self> {
+        let components = s
+            .split(',')
+            .map(|component| component.trim().parse::<T>().map_err(Error::from))
+            .collect::<Result<Vec<_>>>()?;
+
+        let count = components.len();
+
+        components
+            .try_into()
+            .map(Self)
+            .map_err(|_| anyhow!("Expected {} components, found {}: {:?}", N, count, s))
+    }
+}
+
+impl<T: Copy + ops::Add<Output = T>, const N: usize> ops::Add<Vector<T, N>> for Vector<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.zip_with(rhs, |l, r| l + r)
+    }
+}
+
+impl<T: Copy + ops::Add<Output = T>, const N: usize> ops::Add<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: T) -> Self {
+        self.zip_with(Self::splat(rhs), |l, r| l + r)
+    }
+}
+
+impl<T: Copy + ops::AddAssign, const N: usize> ops::AddAssign<Vector<T, N>> for Vector<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *l += *r;
+        }
+    }
+}
+
+impl<T: Copy + ops::AddAssign, const N: usize> ops::AddAssign<T> for Vector<T, N> {
+    fn add_assign(&mut self, rhs: T) {
+        for l in self.0.iter_mut() {
+            *l += rhs;
+        }
+    }
+}
+
+impl<T: Copy + ops::Sub<Output = T>, const N: usize> ops::Sub<Vector<T, N>> for Vector<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_with(rhs, |l, r| l - r)
+    }
+}
+
+impl<T: Copy + ops::Sub<Output = T>, const N: usize> ops::Sub<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self {
+        self.zip_with(Self::splat(rhs), |l, r| l - r)
+    }
+}
+
+impl<T: Copy + ops::SubAssign, const N: usize> ops::SubAssign<Vector<T, N>> for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *l -= *r;
+        }
+    }
+}
+
+impl<T: Copy + ops::SubAssign, const N: usize> ops::SubAssign<T> for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: T) {
+        for l in self.0.iter_mut() {
+            *l -= rhs;
+        }
+    }
+}
+
+impl<T: Copy + ops::Mul<Output = T>, const N: usize> ops::Mul<Vector<T, N>> for Vector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.zip_with(rhs, |l, r| l * r)
+    }
+}
+
+impl<T: Copy + ops::Mul<Output = T>, const N: usize> ops::Mul<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.zip_with(Self::splat(rhs), |l, r| l * r)
+    }
+}
+
+impl<T: Copy + ops::MulAssign, const N: usize> ops::MulAssign<Vector<T, N>> for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *l *= *r;
+        }
+    }
+}
+
+impl<T: Copy + ops::MulAssign, const N: usize> ops::MulAssign<T> for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        for l in self.0.iter_mut() {
+            *l *= rhs;
+        }
+    }
+}
+
+impl<T: Copy + ops::Div<Output = T>, const N: usize> ops::Div<Vector<T, N>> for Vector<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        self.zip_with(rhs, |l, r| l / r)
+    }
+}
+
+impl<T: Copy + ops::Div<Output = T>, const N: usize> ops::Div<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        self.zip_with(Self::splat(rhs), |l, r| l / r)
+    }
+}
+
+impl<T: Copy + ops::DivAssign, const N: usize> ops::DivAssign<Vector<T, N>> for Vector<T, N> {
+    fn div_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *l /= *r;
+        }
+    }
+}
+
+impl<T: Copy + ops::DivAssign, const N: usize> ops::DivAssign<T> for Vector<T, N> {
+    fn div_assign(&mut self, rhs: T) {
+        for l in self.0.iter_mut() {
+            *l /= rhs;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vector_named_components() {
+        let mut v = Vector::<i32, 3>::new(1, 2, 3);
+        assert_eq!((v.x, v.y, v.z), (1, 2, 3));
+
+        v.z = 7;
+        assert_eq!(v, Vector([1, 2, 7]));
+        assert_eq!(v[2], 7);
+
+        let mut v = Vector::<u16, 4>::new(1, 2, 3, 4);
+        v.w += 1;
+        v[0] = 9;
+        assert_eq!(v.as_array(), &[9, 2, 3, 5]);
+    }
+
+    #[test]
+    fn test_vector_ops() {
+        let a = Vector::<i32, 3>::new(1, 2, 3);
+        let b = Vector::<i32, 3>::new(4, 6, 9);
+
+        assert_eq!(a + b, Vector([5, 8, 12]));
+        assert_eq!(b - a, Vector([3, 4, 6]));
+        assert_eq!(a * b, Vector([4, 12, 27]));
+        assert_eq!(b / a, Vector([4, 3, 3]));
+        assert_eq!(a + 1, Vector([2, 3, 4]));
+        assert_eq!(b - 1, Vector([3, 5, 8]));
+        assert_eq!(a * 2, Vector([2, 4, 6]));
+        assert_eq!(b / 2, Vector([2, 3, 4]));
+
+        let mut c = Vector::<u16, 4>::splat(6);
+        c += Vector([1, 2, 3, 4]);
+        c -= 1;
+        c *= Vector([2, 1, 1, 1]);
+        c /= 3;
+        assert_eq!(c, Vector([4, 2, 2, 3]));
+
+        let mut d = Vector::<i64, 4>::default();
+        d -= Vector([1, 0, 0, 1]);
+        d *= -3;
+        d /= Vector([3, 1, 1, 1]);
+        d += 1;
+        assert_eq!(d, Vector([2, 1, 1, 4]));
+    }
+
+    #[test]
+    fn test_vector_map() {
+        let v = Vector::<i32, 3>::new(-1, 2, -3);
+
+        assert_eq!(v.map(i32::abs), Vector([1, 2, 3]));
+        assert_eq!(v.map(|c| c as i64 * 2), Vector::<i64, 3>::new(-2, 4, -6));
+        assert_eq!(v.zip_with(Vector([1, 1, 1]), i32::max), Vector([1, 2, 1]));
+    }
+
+    #[test]
+    fn test_vector_distance() {
+        let a = Vector::<i64, 3>::new(1, -2, 3);
+        let b = Vector::<i64, 3>::new(-1, 4, 3);
+
+        assert_eq!(a.manhattan(b), 8);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(a.abs(), Vector([1, 2, 3]));
+        assert_eq!((a - b).signum(), Vector([1, -1, 0]));
+    }
+
+    #[test]
+    fn test_vector_order() {
+        let mut points = vec![
+            Vector([1, 1]),
+            Vector([0, 1]),
+            Vector([2, 0]),
+            Vector([-1, 2]),
+        ];
+        points.sort();
+
+        assert_eq!(
+            points,
+            vec![
+                Vector([2, 0]),
+                Vector([0, 1]),
+                Vector([1, 1]),
+                Vector([-1, 2])
+            ]
+        );
+        assert!(Vector([0, 0, 1]) > Vector([5, 5, 0]));
+    }
+
+    #[test]
+    fn test_vector_checked() {
+        let a = Vector::<i64, 2>::new(i64::MAX, 1);
+        let b = Vector::<i64, 2>::new(-3, 2);
+
+        assert_eq!(a.checked_add(b), Some(Vector([i64::MAX - 3, 3])));
+        assert_eq!(a.checked_add(Vector([1, 0])), None);
+        assert_eq!(b.checked_sub(Vector([i64::MAX, 0])), None);
+        assert_eq!(b.checked_mul(Vector([2, 3])), Some(Vector([-6, 6])));
+        assert_eq!(a.checked_mul(b), None);
+        assert_eq!(b.checked_div(Vector([3, 2])), Some(Vector([-1, 1])));
+        assert_eq!(b.checked_div(Vector([3, 0])), None);
+        assert_eq!(
+            Vector::<i64, 2>::new(i64::MIN, 0).checked_div(Vector([-1, 1])),
+            None
+        );
+
+        let c = Vector::<u16, 3>::new(u16::MAX, 0, 7);
+        assert_eq!(
+            c.checked_add(Vector([0, 1, 1])),
+            Some(Vector([u16::MAX, 1, 8]))
+        );
+        assert_eq!(c.checked_sub(Vector([0, 1, 0])), None);
+    }
+
+    #[test]
+    fn test_vector_rem_euclid() {
+        let size = Vector::<i64, 2>::new(5, 3);
+
+        assert_eq!(Vector([7, -1]).rem_euclid(size), Vector([2, 2]));
+        assert_eq!(Vector([-5, -6]).rem_euclid(size), Vector([0, 0]));
+        assert_eq!(Vector([1, 1]).checked_rem_euclid(Vector([0, 1])), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vector_rem_euclid_zero() {
+        Vector::<i32, 2>::new(1, 1).rem_euclid(Vector([1, 0]));
+    }
+
+    #[test]
+    fn test_torus() -> Result<()> {
+        let torus = Torus::new(Vector::<i64, 2>::new(5, 3))?;
+
+        assert_eq!(torus.wrap(Vector([5, 3])), Vector([0, 0]));
+        assert_eq!(torus.wrap(Vector([-1, -1])), Vector([4, 2]));
+        assert_eq!(
+            torus.offset(Vector([4, 0]), Vector([1, -1])),
+            Vector([0, 2])
+        );
+        assert_eq!(
+            torus.offset(Vector([2, 1]), Vector([-12, 7])),
+            Vector([0, 2])
+        );
+        // i64::MAX is 2 mod 5
+        assert_eq!(
+            torus.offset(Vector([i64::MAX, 0]), Vector([i64::MAX, 0])),
+            Vector([4, 0])
+        );
+
+        let small = Torus::new(Vector::<u8, 3>::new(200, 10, 1))?;
+        assert_eq!(
+            small.offset(Vector([150, 9, 0]), Vector([100, 3, 5])),
+            Vector([50, 2, 0])
+        );
+
+        assert!(Torus::new(Vector::<i64, 2>::new(5, 0)).is_err());
+        assert!(Torus::new(Vector::<i64, 2>::new(-5, 3)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_parse() -> Result<()> {
+        assert_eq!("1,-2,3".parse::<Vector<i32, 3>>()?, Vector([1, -2, 3]));
+        assert_eq!(Vector::<u16, 4>::new(1, 2, 3, 4).to_string(), "1,2,3,4");
+        assert!("1,2".parse::<Vector<i32, 3>>().is_err());
+        assert!("1,2,x".parse::<Vector<i32, 3>>().is_err());
+        assert!("1,-2,3".parse::<Vector<u16, 3>>().is_err());
+
+        Ok(())
+    }
+}
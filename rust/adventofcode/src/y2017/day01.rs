@@ -0,0 +1,543 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Clap;
+use tracing::debug;
+
+use super::{input, YEAR};
+use crate::Command;
+
+#[derive(Debug, Clap)]
+pub enum Args {
+    Part1(Options),
+    Part2(Options),
+    /// Sum the digits matching the one `offset` places further round the list, reading the
+    /// input as a stream
+    Captcha {
+        offset: usize,
+
+        #[clap(flatten)]
+        options: Options,
+    },
+}
+
+#[derive(Debug, Clap)]
+pub struct Options {
+    /// The base the digits are written in, up to 36
+    #[clap(long, default_value = "10")]
+    radix: u32,
+}
+
+impl Command for Args {
+    fn execute(&self) -> Result<String> {
+        match self {
+            Self::Part1(options) => part_one(options),
+            Self::Part2(options) => part_two(options),
+            Self::Captcha { offset, options } => {
+                let path = crate::input_path(YEAR, "day01");
+                let file = File::open(&path).with_context(|| path.clone())?;
+
+                captcha_reader(BufReader::new(file), *offset, digit_parser(options.radix)?)
+                    .with_context(|| path)
+                    .map(|sum| sum.to_string())
+            }
+        }
+    }
+}
+
+/// Map bytes to their digit values in `radix`, ignoring case
+fn digit_parser(radix: u32) -> Result<impl Fn(u8) -> Option<u32>> {
+    if !(2..=36).contains(&radix) {
+        return Err(anyhow!("Radix must be between 2 and 36: {}", radix));
+    }
+
+    Ok(move |byte: u8| (byte as char).to_digit(radix))
+}
+
+/// Sum the values of the digits that match the digit `offset` places after them, wrapping
+/// around the end of the list
+///
+/// `digit` maps each byte to its value, and digits match when their values do.
+fn captcha<F>(bytes: &[u8], offset: usize, digit: F) -> Result<i64>
+where
+    F: Fn(u8) -> Option<u32>,
+{
+    let values = bytes
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| {
+            digit(*byte).ok_or_else(|| anyhow!("Not a digit at {}: {:?}", i, *byte as char))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let sum = matching_sum(&values, offset);
+
+    debug!(len = values.len(), offset, sum, "Captcha");
+
+    Ok(sum)
+}
+
+fn matching_sum(values: &[u32], offset: usize) -> i64 {
+    let ahead = values.iter().cycle().skip(offset % values.len().max(1));
+
+    values
+        .iter()
+        .zip(ahead)
+        .filter(|(l, r)| l == r)
+        .map(|(l, _)| *l as i64)
+        .sum()
+}
+
+/// `captcha` over the digits read from `reader`, holding only about `2 * offset` of them
+///
+/// Each digit is compared with the one `offset` before it as it arrives, and the first
+/// `offset` digits are kept to compare the last ones against once the list wraps around.
+/// Whitespace before and after the digits is skipped, like the trimmed puzzle input.
+fn captcha_reader<R, F>(reader: R, offset: usize, digit: F) -> Result<i64>
+where
+    R: BufRead,
+    F: Fn(u8) -> Option<u32>,
+{
+    let mut first = Vec::new();
+    let mut last = VecDeque::new();
+    let mut len = 0;
+    let mut sum = 0;
+    let mut space = None;
+
+    for (i, byte) in reader.bytes().enumerate() {
+        let byte = byte?;
+
+        if byte.is_ascii_whitespace() {
+            if len > 0 && space.is_none() {
+                space = Some((i, byte));
+            }
+
+            continue;
+        }
+
+        // Whitespace followed by more digits wasn't trailing after all
+        if let Some((i, byte)) = space {
+            return Err(anyhow!("Not a digit at {}: {:?}", i, byte as char));
+        }
+
+        let value =
+            digit(byte).ok_or_else(|| anyhow!("Not a digit at {}: {:?}", i, byte as char))?;
+
+        if len < offset {
+            first.push(value);
+        }
+
+        last.push_back(value);
+
+        if last.len() > offset {
+            let earlier = last.pop_front().unwrap();
+
+            if earlier == value {
+                sum += earlier as i64;
+            }
+        }
+
+        len += 1;
+    }
+
+    if len <= offset {
+        // The offset goes round more than once, so it needs the length, and everything fit
+        sum = matching_sum(&first, offset);
+    } else {
+        sum += last
+            .iter()
+            .zip(&first)
+            .filter(|(l, r)| l == r)
+            .map(|(l, _)| *l as i64)
+            .sum::<i64>();
+    }
+
+    debug!(len, offset, sum, "Captcha");
+
+    Ok(sum)
+}
+
+/// `captcha` for radixes up to 10, where digits match exactly when their bytes do, so the
+/// bytes can be compared directly with `simd::matching_sum`
+fn captcha_fast(bytes: &[u8], offset: usize, radix: u32) -> Result<i64> {
+    let digit = digit_parser(radix)?;
+
+    if radix > 10 {
+        return captcha(bytes, offset, digit);
+    }
+
+    simd::matching_sum(bytes, offset, radix as u8)
+        .map(|sum| sum as i64)
+        .map_err(|i| anyhow!("Not a digit at {}: {:?}", i, bytes[i] as char))
+}
+
+/// Run the captcha over the puzzle input with the offset picked from its length
+fn solve<F>(options: &Options, offset: F) -> Result<i64>
+where
+    F: Fn(usize) -> usize,
+{
+    let input = input("day01")?;
+    let bytes = input.trim().as_bytes();
+
+    captcha_fast(bytes, offset(bytes.len()), options.radix)
+}
+
+/// Vectorized matching for ASCII digit strings, picking the widest instructions the CPU has
+mod simd {
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    /// Sum the digit values of the bytes matching the byte `offset` places after them,
+    /// wrapping around, or give the index of the first byte that isn't a digit in `radix`,
+    /// which is at most 10
+    pub fn matching_sum(bytes: &[u8], offset: usize, radix: u8) -> Result<u64, usize> {
+        if bytes.is_empty() {
+            return Ok(0);
+        }
+
+        let split = bytes.len() - offset % bytes.len();
+
+        // The bytes before `split` are compared with ones further on, the rest wrap around,
+        // so every byte is on the left, and checked, exactly once
+        let before = sum_matches(&bytes[..split], &bytes[bytes.len() - split..], radix)?;
+        let after = sum_matches(&bytes[split..], &bytes[..bytes.len() - split], radix)
+            .map_err(|i| split + i)?;
+
+        Ok(before + after)
+    }
+
+    /// Sum `l - b'0'` for each pair of equal bytes `l` and `r`, or give the index of the
+    /// first `l` that isn't a digit in `radix`
+    fn sum_matches(l: &[u8], r: &[u8], radix: u8) -> Result<u64, usize> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                // SAFETY: the CPU supports AVX2
+                return unsafe { sum_matches_avx2(l, r, radix) };
+            }
+
+            // SAFETY: SSE2 is part of the x86_64 baseline
+            unsafe { sum_matches_sse2(l, r, radix) }
+        }
+
+        #[cfg(not(target_arch = "x86_64"))]
+        sum_matches_scalar(l, r, radix)
+    }
+
+    pub fn sum_matches_scalar(l: &[u8], r: &[u8], radix: u8) -> Result<u64, usize> {
+        let mut sum = 0;
+
+        for (i, (l, r)) in l.iter().zip(r).enumerate() {
+            let value = l.wrapping_sub(b'0');
+
+            if value >= radix {
+                return Err(i);
+            }
+
+            if l == r {
+                sum += value as u64;
+            }
+        }
+
+        Ok(sum)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sum_matches_avx2(l: &[u8], r: &[u8], radix: u8) -> Result<u64, usize> {
+        const LANES: usize = 32;
+
+        let len = l.len().min(r.len());
+        let chunks = len / LANES;
+        let zero = _mm256_set1_epi8(b'0' as i8);
+        let highest = _mm256_set1_epi8(radix as i8 - 1);
+        let mut totals = _mm256_setzero_si256();
+
+        for i in 0..chunks {
+            let a = _mm256_loadu_si256(l.as_ptr().add(i * LANES) as *const __m256i);
+            let b = _mm256_loadu_si256(r.as_ptr().add(i * LANES) as *const __m256i);
+
+            // Bytes below `'0'` wrap round to large values, so the digits are exactly the
+            // values that are no more than `highest` as unsigned bytes
+            let values = _mm256_sub_epi8(a, zero);
+            let digits = _mm256_cmpeq_epi8(_mm256_max_epu8(values, highest), highest);
+            let invalid = !(_mm256_movemask_epi8(digits) as u32);
+
+            if invalid != 0 {
+                return Err(i * LANES + invalid.trailing_zeros() as usize);
+            }
+
+            // `sad` against zero adds each group of 8 bytes into a 64 bit lane
+            let matched = _mm256_and_si256(values, _mm256_cmpeq_epi8(a, b));
+            totals = _mm256_add_epi64(totals, _mm256_sad_epu8(matched, _mm256_setzero_si256()));
+        }
+
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, totals);
+
+        let tail = chunks * LANES;
+        let rest = sum_matches_scalar(&l[tail..len], &r[tail..len], radix).map_err(|i| tail + i)?;
+
+        Ok(lanes.iter().sum::<u64>() + rest)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn sum_matches_sse2(l: &[u8], r: &[u8], radix: u8) -> Result<u64, usize> {
+        const LANES: usize = 16;
+
+        let len = l.len().min(r.len());
+        let chunks = len / LANES;
+        let zero = _mm_set1_epi8(b'0' as i8);
+        let highest = _mm_set1_epi8(radix as i8 - 1);
+        let mut totals = _mm_setzero_si128();
+
+        for i in 0..chunks {
+            let a = _mm_loadu_si128(l.as_ptr().add(i * LANES) as *const __m128i);
+            let b = _mm_loadu_si128(r.as_ptr().add(i * LANES) as *const __m128i);
+
+            let values = _mm_sub_epi8(a, zero);
+            let digits = _mm_cmpeq_epi8(_mm_max_epu8(values, highest), highest);
+            let invalid = !(_mm_movemask_epi8(digits) as u32) & 0xffff;
+
+            if invalid != 0 {
+                return Err(i * LANES + invalid.trailing_zeros() as usize);
+            }
+
+            let matched = _mm_and_si128(values, _mm_cmpeq_epi8(a, b));
+            totals = _mm_add_epi64(totals, _mm_sad_epu8(matched, _mm_setzero_si128()));
+        }
+
+        let mut lanes = [0u64; 2];
+        _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, totals);
+
+        let tail = chunks * LANES;
+        let rest = sum_matches_scalar(&l[tail..len], &r[tail..len], radix).map_err(|i| tail + i)?;
+
+        Ok(lanes.iter().sum::<u64>() + rest)
+    }
+}
+
+fn part_one(options: &Options) -> Result<String> {
+    Ok(solve(options, |_| 1)?.to_string())
+}
+
+fn part_two(options: &Options) -> Result<String> {
+    Ok(solve(options, |len| len / 2)?.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rng::XorShift;
+
+    fn digitization_quarantine(input: &str) -> i64 {
+        captcha(input.as_bytes(), 1, digit_parser(10).unwrap()).unwrap()
+    }
+
+    fn digitization_quarantine_circular(input: &str) -> i64 {
+        captcha(input.as_bytes(), input.len() / 2, digit_parser(10).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        /*
+        1122 produces a sum of 3 (1 + 2) because the first digit (1) matches the second digit and the third digit (2) matches the fourth digit.
+        1111 produces 4 because each digit (all 1) matches the next.
+        1234 produces 0 because no digit matches the next.
+        91212129 produces 9 because the only digit that matches the next one is the last digit, 9.
+        */
+        assert_eq!(digitization_quarantine("1122"), 3);
+        assert_eq!(digitization_quarantine("1111"), 4);
+        assert_eq!(digitization_quarantine("1234"), 0);
+        assert_eq!(digitization_quarantine("91212129"), 9);
+        Ok(())
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_two() -> Result<()> {
+        /*
+        1212 produces 6: the list contains 4 items, and all four digits match the digit 2 items ahead.
+        1221 produces 0, because every comparison is between a 1 and a 2.
+        123425 produces 4, because both 2s match each other, but no other digit has a match.
+        123123 produces 12.
+        12131415 produces 4.
+        */
+        assert_eq!(digitization_quarantine_circular("1212"), 6);
+        assert_eq!(digitization_quarantine_circular("1221"), 0);
+        assert_eq!(digitization_quarantine_circular("123425"), 4);
+        assert_eq!(digitization_quarantine_circular("123123"), 12);
+        assert_eq!(digitization_quarantine_circular("12131415"), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_captcha() -> Result<()> {
+        let decimal = digit_parser(10)?;
+
+        assert_eq!(captcha(b"", 1, &decimal)?, 0);
+        assert_eq!(captcha(b"7", 1, &decimal)?, 7);
+        assert_eq!(captcha(b"12312", 3, &decimal)?, 3);
+        assert_eq!(captcha(b"1122", 5, &decimal)?, 3);
+        assert_eq!(captcha(b"1122", 0, &decimal)?, 6);
+
+        let err = captcha(b"1122\n", 1, &decimal).unwrap_err();
+        assert_eq!(err.to_string(), "Not a digit at 4: '\\n'");
+        assert!(captcha(b"11a", 1, &decimal).is_err());
+
+        assert_eq!(captcha(b"aAfF", 1, digit_parser(16)?)?, 10 + 15);
+        assert!(digit_parser(1).is_err());
+        assert!(digit_parser(37).is_err());
+
+        // Any mapping works, e.g. letters by their position in the alphabet
+        let letters = |byte: u8| byte.checked_sub(b'a').map(|value| value as u32 + 1);
+        assert_eq!(captcha(b"zaz", 1, letters)?, 26);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_captcha_reader() -> Result<()> {
+        let decimal = digit_parser(10)?;
+
+        for len in 0..40 {
+            let bytes = random_digits(len, 3, len as u64 + 1);
+
+            for offset in 0..=2 * len + 1 {
+                assert_eq!(
+                    captcha_reader(&bytes[..], offset, &decimal)?,
+                    captcha(&bytes, offset, &decimal)?,
+                    "len {} offset {}",
+                    len,
+                    offset
+                );
+            }
+        }
+
+        assert_eq!(captcha_reader(&b"\n 1122\r\n"[..], 1, &decimal)?, 3);
+
+        let err = captcha_reader(&b"11 22"[..], 1, &decimal).unwrap_err();
+        assert_eq!(err.to_string(), "Not a digit at 2: ' '");
+        assert!(captcha_reader(&b"11a"[..], 1, &decimal).is_err());
+
+        Ok(())
+    }
+
+    /// A deterministic stream of digits, so failures can be reproduced
+    fn random_digits(len: usize, alphabet: u8, seed: u64) -> Vec<u8> {
+        let mut rng = XorShift::new(seed);
+
+        (0..len)
+            .map(|_| b'0' + rng.below(alphabet as u64) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn test_simd_small() -> Result<()> {
+        for len in 0..100 {
+            let bytes = random_digits(len, 3, len as u64 + 1);
+
+            for offset in 0..=len + 1 {
+                assert_eq!(
+                    captcha_fast(&bytes, offset, 10)?,
+                    captcha(&bytes, offset, digit_parser(10)?)?,
+                    "len {} offset {}",
+                    len,
+                    offset
+                );
+            }
+        }
+
+        assert!(captcha_fast(b"1122\n", 1, 10).is_err());
+        assert!(captcha_fast(b"1192", 1, 8).is_err());
+        assert_eq!(captcha_fast(b"aAfF", 1, 16)?, 25);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simd_invalid() -> Result<()> {
+        let bytes = random_digits(200, 8, 5);
+
+        // Bytes either side of the digits, one that's only a digit in a larger radix, and
+        // one that would pass a signed comparison
+        for bad in [b'/', b':', b'8', 0x80].iter().copied() {
+            for at in [0, 1, 15, 16, 31, 32, 63, 100, 190, 199].iter().copied() {
+                let mut bytes = bytes.clone();
+                bytes[at] = bad;
+
+                for offset in [0, 1, 17, 100].iter().copied() {
+                    assert_eq!(simd::matching_sum(&bytes, offset, 8), Err(at));
+
+                    #[cfg(target_arch = "x86_64")]
+                    {
+                        assert_eq!(
+                            unsafe { simd::sum_matches_sse2(&bytes, &bytes, 8) },
+                            Err(at)
+                        );
+
+                        if is_x86_feature_detected!("avx2") {
+                            let avx2 = unsafe { simd::sum_matches_avx2(&bytes, &bytes, 8) };
+                            assert_eq!(avx2, Err(at));
+                        }
+                    }
+                }
+
+                assert_eq!(
+                    captcha_fast(&bytes, 1, 8).unwrap_err().to_string(),
+                    captcha(&bytes, 1, digit_parser(8)?)
+                        .unwrap_err()
+                        .to_string()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simd_differential() -> Result<()> {
+        // Small alphabets make matches common, large ones make them rare
+        for (seed, alphabet) in [(1, 2), (7, 3), (42, 10)].iter().copied() {
+            let len = 2 * 1024 * 1024 + seed as usize;
+            let bytes = random_digits(len, alphabet, seed);
+
+            assert_eq!(
+                captcha_fast(&bytes, 1, 10)?,
+                captcha(&bytes, 1, digit_parser(10)?)?
+            );
+
+            for offset in [0, 33, len / 2, len - 1].iter().copied() {
+                let split = len - offset % len;
+                let pairs = [
+                    (&bytes[..split], &bytes[len - split..]),
+                    (&bytes[split..], &bytes[..len - split]),
+                ];
+                let mut scalar = 0;
+
+                for (l, r) in pairs.iter() {
+                    let expected = simd::sum_matches_scalar(l, r, 10);
+
+                    #[cfg(target_arch = "x86_64")]
+                    {
+                        assert_eq!(unsafe { simd::sum_matches_sse2(l, r, 10) }, expected);
+
+                        if is_x86_feature_detected!("avx2") {
+                            assert_eq!(unsafe { simd::sum_matches_avx2(l, r, 10) }, expected);
+                        }
+                    }
+
+                    scalar += expected.unwrap();
+                }
+
+                assert_eq!(simd::matching_sum(&bytes, offset, 10), Ok(scalar));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,545 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Error, Result};
+use clap::Clap;
+use tracing::debug;
+
+use super::input;
+use crate::Command;
+
+#[derive(Debug, Clap)]
+pub enum Args {
+    Part1 {
+        /// How to checksum each row
+        #[clap(long, default_value = "range", possible_values = Checksum::NAMES)]
+        checksum: Checksum,
+    },
+    Part2 {
+        /// How to checksum each row
+        #[clap(long, default_value = "division", possible_values = Checksum::NAMES)]
+        checksum: Checksum,
+    },
+    /// List every evenly dividing pair in each row of the input
+    Pairs,
+}
+
+impl Command for Args {
+    fn execute(&self) -> Result<String> {
+        match self {
+            Self::Part1 { checksum } | Self::Part2 { checksum } => solve(*checksum),
+            Self::Pairs => diagnose(&parse_spreadsheet(&input("day02")?)?),
+        }
+    }
+}
+
+/// Parse a spreadsheet with one row per line, its values separated by commas, tabs or runs
+/// of spaces
+fn parse_spreadsheet(sheet: &str) -> Result<Vec<Vec<i64>>> {
+    sheet
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(row, line)| {
+            let cells: Box<dyn Iterator<Item = &str>> = if line.contains(',') {
+                Box::new(line.split(',').map(str::trim))
+            } else {
+                Box::new(line.split_whitespace())
+            };
+
+            cells
+                .enumerate()
+                .map(|(column, cell)| {
+                    cell.parse()
+                        .with_context(|| format!("Row {} column {}: {:?}", row, column, cell))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The ways to reduce a row to a single number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checksum {
+    /// The largest value minus the smallest
+    Range,
+    /// The quotient of the only two values that divide evenly
+    Division,
+    Sum,
+    /// The total distance of the values from their median
+    MedianSpread,
+}
+
+impl Checksum {
+    const NAMES: &'static [&'static str] = &["range", "division", "sum", "median-spread"];
+
+    fn row(self, row: &[i64]) -> Result<i64> {
+        match self {
+            Self::Range => row_checksum(row),
+            Self::Division => row_div_checksum(row),
+            Self::Sum => Ok(row.iter().sum()),
+            Self::MedianSpread => row_median_spread(row),
+        }
+    }
+}
+
+impl FromStr for Checksum {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "range" => Ok(Self::Range),
+            "division" => Ok(Self::Division),
+            "sum" => Ok(Self::Sum),
+            "median-spread" => Ok(Self::MedianSpread),
+            _ => Err(anyhow!("Unknown checksum: {}", s)),
+        }
+    }
+}
+
+fn row_checksum(row: &[i64]) -> Result<i64> {
+    let min = row.iter().min().context("Empty row")?;
+    let max = row.iter().max().context("Empty row")?;
+
+    debug!(min, max, "Range");
+
+    Ok(max - min)
+}
+
+/// Distances are measured from the lower median, which minimizes their total just as well
+fn row_median_spread(row: &[i64]) -> Result<i64> {
+    let mut sorted = row.to_vec();
+    sorted.sort_unstable();
+
+    let median = *sorted
+        .get((sorted.len().max(1) - 1) / 2)
+        .context("Empty row")?;
+
+    Ok(sorted.iter().map(|value| (value - median).abs()).sum())
+}
+
+/// How many of the leading values of `sorted` are below `target`
+///
+/// This gallops from the front before binary searching, so it costs about `log k`
+/// comparisons for an answer of `k`, which keeps stepping through values one at a time cheap.
+fn count_below(sorted: &[i64], target: i64) -> usize {
+    let mut end = 1;
+
+    while end < sorted.len() && sorted[end - 1] < target {
+        end *= 2;
+    }
+
+    let start = end / 2;
+    let end = end.min(sorted.len());
+
+    start + sorted[start..end].partition_point(|value| *value < target)
+}
+
+/// Every pair of distinct values in the row where the first evenly divides the second,
+/// ordered by divisor and then multiple
+fn divisible_pairs(values: &[i64]) -> Result<Vec<(i64, i64)>> {
+    first_divisible_pairs(values, usize::MAX)
+}
+
+/// The first `limit` pairs `divisible_pairs` would find, stopping the search there
+///
+/// For each divisor this jumps through the larger values from one multiple to the next,
+/// galloping ahead to the first value at or past each. A divisor takes one step per gap
+/// between its multiples that holds a value, so at most `min(max / divisor, larger values)`
+/// steps, and skipping `k` values in a step costs `log k`. Rows of similar values, or with a
+/// few outliers, come out close to `n log n`.
+///
+/// The worst case is still quadratic, no better than testing every pair: when values are
+/// spread over many orders of magnitude, most of them land in a gap of their own for every
+/// smaller divisor. A small `limit` only helps rows that have that many pairs to find, as
+/// proving there are fewer means searching the whole row.
+fn first_divisible_pairs(values: &[i64], limit: usize) -> Result<Vec<(i64, i64)>> {
+    if let Some(value) = values.iter().find(|value| **value <= 0) {
+        return Err(anyhow!("Only positive values can be divided: {}", value));
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut pairs = Vec::new();
+
+    for (i, divisor) in sorted.iter().enumerate() {
+        let mut larger = &sorted[i + 1..];
+        let mut multiple = divisor.checked_mul(2);
+
+        while let Some(target) = multiple {
+            if pairs.len() >= limit {
+                debug!(?pairs, "Found enough");
+                return Ok(pairs);
+            }
+
+            larger = &larger[count_below(larger, target)..];
+
+            let value = match larger.first() {
+                Some(value) => *value,
+                None => break,
+            };
+
+            if value == target {
+                pairs.push((*divisor, value));
+            }
+
+            // The next multiple to look for, skipping any that fall short of `value`
+            let skip = (value == target || value % divisor != 0) as i64;
+
+            multiple = (value / divisor)
+                .checked_add(skip)
+                .and_then(|next| next.checked_mul(*divisor));
+        }
+    }
+
+    debug!(?pairs, "Found");
+
+    Ok(pairs)
+}
+
+fn format_pairs(pairs: &[(i64, i64)]) -> String {
+    pairs
+        .iter()
+        .map(|(divisor, multiple)| format!("{}/{}", multiple, divisor))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The search stops at a second pair, which is enough to show the row is ambiguous; `diagnose`
+/// lists them all
+fn row_div_checksum(values: &[i64]) -> Result<i64> {
+    match first_divisible_pairs(values, 2)?.as_slice() {
+        [(divisor, multiple)] => Ok(multiple / divisor),
+        [] => Err(anyhow!("No values divide evenly")),
+        pairs => Err(anyhow!(
+            "At least two pairs divide evenly: {}",
+            format_pairs(pairs)
+        )),
+    }
+}
+
+/// List the evenly dividing pairs of every row, flagging rows without exactly one
+fn diagnose(sheet: &[Vec<i64>]) -> Result<String> {
+    let rows = sheet
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let pairs = divisible_pairs(row).with_context(|| format!("Row {}", i))?;
+            let note = match pairs.len() {
+                0 => "(none)",
+                1 => "",
+                _ => " (ambiguous)",
+            };
+
+            Ok(format!("{}: {}{}", i, format_pairs(&pairs), note))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(rows.join("\n"))
+}
+
+fn grid_checksum(sheet: &[Vec<i64>], checksum: Checksum) -> Result<i64> {
+    let rows = sheet
+        .iter()
+        .enumerate()
+        .map(|(i, row)| checksum.row(row).with_context(|| format!("Row {}", i)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(rows.iter().sum())
+}
+
+fn solve(checksum: Checksum) -> Result<String> {
+    let sheet = parse_spreadsheet(&input("day02")?)?;
+
+    grid_checksum(&sheet, checksum).map(|i| i.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::rng::XorShift;
+
+    /// `divisible_pairs` by testing every pair, as a reference to compare against
+    fn divisible_pairs_pairwise(values: &[i64]) -> Vec<(i64, i64)> {
+        let mut pairs = values
+            .iter()
+            .flat_map(|l| values.iter().map(move |r| (*l, *r)))
+            .filter(|(l, r)| l < r && r % l == 0)
+            .collect::<Vec<_>>();
+
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        pairs
+    }
+
+    /// Rows of `width` values from one half-open octave, which never divide each other, with
+    /// a doubled value planted in each so they have a pair to find
+    fn benchmark_sheet(rows: usize, width: usize) -> Vec<Vec<i64>> {
+        const LOW: u64 = 1 << 40;
+
+        let mut rng = XorShift::new(XorShift::SEED);
+        let mut random = move || (LOW + rng.below(LOW)) as i64;
+
+        (0..rows)
+            .map(|_| {
+                let mut row = (0..width.max(1)).map(|_| random()).collect::<Vec<_>>();
+                row[0] = row[row.len() / 2] * 2;
+                row
+            })
+            .collect()
+    }
+
+    /// Rows of `width` values whose magnitudes are spread evenly from 1 up to 2^50, so many of
+    /// them divide each other, each with an outlier of 10^15 to stretch the range further
+    fn wide_benchmark_sheet(rows: usize, width: usize) -> Vec<Vec<i64>> {
+        let mut rng = XorShift::new(XorShift::SEED);
+
+        (0..rows)
+            .map(|_| {
+                let mut row = (0..width.max(1))
+                    .map(|_| {
+                        let bits = 1 + rng.below(50);
+                        1 + rng.below(1 << bits) as i64
+                    })
+                    .collect::<Vec<_>>();
+                row[0] = 1_000_000_000_000_000;
+                row
+            })
+            .collect()
+    }
+
+    /// Rows of `1..=width` in a random order, with an outlier of 10^15 in place of one of them
+    fn dense_benchmark_sheet(rows: usize, width: usize) -> Vec<Vec<i64>> {
+        let mut rng = XorShift::new(XorShift::SEED);
+
+        (0..rows)
+            .map(|_| {
+                let mut row = (1..=width.max(1) as i64).collect::<Vec<_>>();
+
+                for i in (1..row.len()).rev() {
+                    row.swap(i, rng.below(i as u64 + 1) as usize);
+                }
+
+                row[0] = 1_000_000_000_000_000;
+                row
+            })
+            .collect()
+    }
+
+    /// Time the sorted and pairwise divisor searches against each other on one sheet
+    fn benchmark_searches(sheet: &[Vec<i64>]) -> Result<String> {
+        let start = Instant::now();
+        let sorted = sheet
+            .iter()
+            .map(|row| divisible_pairs(row))
+            .collect::<Result<Vec<_>>>()?;
+        let sorted_elapsed = start.elapsed();
+
+        let start = Instant::now();
+        let pairwise = sheet
+            .iter()
+            .map(|row| divisible_pairs_pairwise(row))
+            .collect::<Vec<_>>();
+        let pairwise_elapsed = start.elapsed();
+
+        if sorted != pairwise {
+            return Err(anyhow!("The searches disagree"));
+        }
+
+        Ok(format!(
+            "sorted:   {:?}\npairwise: {:?}",
+            sorted_elapsed, pairwise_elapsed
+        ))
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_one() -> Result<()> {
+        /*
+        For example, given the following spreadsheet:
+
+        5 1 9 5
+        7 5 3
+        2 4 6 8
+
+        The first row's largest and smallest values are 9 and 1, and their difference is 8.
+        The second row's largest and smallest values are 7 and 3, and their difference is 4.
+        The third row's difference is 6.
+
+
+        In this example, the spreadsheet's checksum would be 8 + 4 + 6 = 18.
+        */
+        assert_eq!(row_checksum(&[5, 1, 9, 5])?, 8);
+        assert_eq!(row_checksum(&[7, 5, 3])?, 4);
+        assert_eq!(row_checksum(&[2, 4, 6, 8])?, 6);
+
+        assert_eq!(
+            grid_checksum(
+                &parse_spreadsheet("5\t1\t9\t5\n7\t5\t3\n2\t4\t6\t8\n")?,
+                Checksum::Range
+            )?,
+            18
+        );
+
+        Ok(())
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_two() -> Result<()> {
+        /*
+        For example, given the following spreadsheet:
+
+        5 9 2 8
+        9 4 7 3
+        3 8 6 5
+
+            In the first row, the only two numbers that evenly divide are 8 and 2; the result of this division is 4.
+            In the second row, the two numbers are 9 and 3; the result is 3.
+            In the third row, the result is 2.
+
+        In this example, the sum of the results would be 4 + 3 + 2 = 9.
+        */
+        assert_eq!(row_div_checksum(&[5, 9, 2, 8])?, 4);
+        assert_eq!(row_div_checksum(&[9, 4, 7, 3])?, 3);
+        assert_eq!(row_div_checksum(&[3, 8, 6, 5])?, 2);
+
+        assert_eq!(
+            grid_checksum(
+                &parse_spreadsheet("5\t9\t2\t8\n9\t4\t7\t3\n3\t8\t6\t5\n")?,
+                Checksum::Division
+            )?,
+            9
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_divisible_pairs() -> Result<()> {
+        assert_eq!(divisible_pairs(&[5, 9, 2, 8])?, vec![(2, 8)]);
+        assert_eq!(divisible_pairs(&[2, 2, 4])?, vec![(2, 4)]);
+        assert_eq!(divisible_pairs(&[])?, vec![]);
+        assert_eq!(
+            divisible_pairs(&[12, 5, 3, 6])?,
+            vec![(3, 6), (3, 12), (6, 12)]
+        );
+        assert!(divisible_pairs(&[4, 0, 2]).is_err());
+
+        assert!(row_div_checksum(&[5, 7, 9]).is_err());
+        assert!(row_div_checksum(&[12, 5, 3, 6]).is_err());
+        assert_eq!(
+            first_divisible_pairs(&[12, 5, 3, 6], 2)?,
+            vec![(3, 6), (3, 12)]
+        );
+        assert_eq!(first_divisible_pairs(&[5, 9, 2, 8], 2)?, vec![(2, 8)]);
+        assert!(row_div_checksum(&[1, 1]).is_err());
+
+        assert_eq!(
+            diagnose(&[vec![5, 9, 2, 8], vec![5, 7], vec![12, 3, 6]])?,
+            "0: 8/2\n1: (none)\n2: 6/3, 12/3, 12/6 (ambiguous)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_divisible_pairs_pairwise() -> Result<()> {
+        // From rows where most values are multiples of others to rows where hardly any are
+        for (max, width) in [(10, 30), (100, 30), (1000, 50), (1_000_000, 20)].iter() {
+            let mut rng = XorShift::new(*max as u64);
+
+            for _ in 0..50 {
+                let row = (0..*width)
+                    .map(|_| 1 + rng.below(*max as u64) as i64)
+                    .collect::<Vec<_>>();
+
+                assert_eq!(divisible_pairs(&row)?, divisible_pairs_pairwise(&row));
+            }
+        }
+
+        for row in benchmark_sheet(3, 200) {
+            assert_eq!(divisible_pairs(&row)?.len(), 1);
+        }
+
+        for row in dense_benchmark_sheet(2, 300)
+            .into_iter()
+            .chain(wide_benchmark_sheet(5, 300))
+        {
+            assert_eq!(divisible_pairs(&row)?, divisible_pairs_pairwise(&row));
+        }
+
+        // Dense small values with outliers, right up to where the multiples overflow
+        let mut row = (1..=500).collect::<Vec<_>>();
+        row.extend(&[1_000_000_000_000_000, i64::MAX / 2, i64::MAX - 1, i64::MAX]);
+        assert_eq!(divisible_pairs(&row)?, divisible_pairs_pairwise(&row));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_spreadsheet() -> Result<()> {
+        let expected = vec![vec![5, 1, 9, 5], vec![7, 5, 3], vec![-2, 400, 6, 8]];
+
+        assert_eq!(
+            parse_spreadsheet("5\t1\t9\t5\n7\t5\t3\n-2\t400\t6\t8\n")?,
+            expected
+        );
+        assert_eq!(
+            parse_spreadsheet("5  1 9   5\n 7 5\t3\n\n-2 400 6 8")?,
+            expected
+        );
+        assert_eq!(
+            parse_spreadsheet("5,1,9,5\r\n7, 5, 3\r\n-2,400,6,8\r\n")?,
+            expected
+        );
+
+        assert!(parse_spreadsheet("5,,9").is_err());
+        assert!(parse_spreadsheet("5 x 9").is_err());
+        assert_eq!(parse_spreadsheet("")?, Vec::<Vec<i64>>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checksums() -> Result<()> {
+        let sheet = parse_spreadsheet("300 -20 1000\n5 1 9 5")?;
+
+        assert_eq!(grid_checksum(&sheet, Checksum::Range)?, 1020 + 8);
+        assert_eq!(grid_checksum(&sheet, Checksum::Sum)?, 1280 + 20);
+        assert_eq!(grid_checksum(&sheet, Checksum::MedianSpread)?, 1020 + 8);
+        assert_eq!(row_median_spread(&[1, 2, 3, 10])?, 10);
+        assert!(row_checksum(&[]).is_err());
+        assert!(row_median_spread(&[]).is_err());
+
+        for name in Checksum::NAMES {
+            assert!(name.parse::<Checksum>().is_ok());
+        }
+
+        Ok(())
+    }
+
+    /// Time the divisor searches on rows of similar values, small values with an outlier, and
+    /// values of every magnitude, which is the worst case for the sorted search. Run it with
+    /// `cargo test --release -- --ignored --nocapture bench_divisible_pairs`
+    #[test]
+    #[ignore]
+    fn bench_divisible_pairs() -> Result<()> {
+        let (rows, width) = (10, 5000);
+
+        println!(
+            "{} rows of {} values\none octave:\n{}\ndense with an outlier:\n{}\nwide range:\n{}",
+            rows,
+            width,
+            benchmark_searches(&benchmark_sheet(rows, width))?,
+            benchmark_searches(&dense_benchmark_sheet(rows, width))?,
+            benchmark_searches(&wide_benchmark_sheet(rows, width))?
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,579 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+use clap::Clap;
+use tracing::debug;
+
+use super::number_input;
+use crate::direction::Direction;
+use crate::rect::Rect;
+use crate::{Command, Point};
+
+#[derive(Debug, Clap)]
+pub enum Args {
+    Part1 {
+        /// The square to carry data from, instead of reading it from the input file
+        square: Option<usize>,
+    },
+    Part2 {
+        /// The value to exceed, instead of reading it from the input file
+        limit: Option<i64>,
+    },
+    /// Draw the first squares of the spiral the way the puzzle does
+    Render {
+        /// How many squares to draw
+        #[clap(default_value = "25")]
+        count: usize,
+
+        /// Which numbers to write in the squares
+        #[clap(long, default_value = "index", possible_values = &["index", "sums"])]
+        values: Values,
+
+        #[clap(flatten)]
+        fill: FillArgs,
+    },
+    /// Print the first terms of a spiral fill, e.g. to look up in the OEIS
+    Sequence {
+        /// How many terms to print
+        #[clap(default_value = "25")]
+        count: usize,
+
+        #[clap(flatten)]
+        fill: FillArgs,
+    },
+}
+
+/// How `sums` squares are filled in; part two is the default
+#[derive(Debug, Clap)]
+pub struct FillArgs {
+    /// Neighbours to read: 4, 8, knight, or offsets such as "1,0;0,-1"
+    #[clap(long, default_value = "8")]
+    stencil: Stencil,
+
+    /// How to combine the neighbours' values
+    #[clap(long, default_value = "sum", possible_values = &["sum", "product", "max", "count"])]
+    aggregate: Aggregate,
+
+    /// The value of the first square
+    #[clap(long, default_value = "1")]
+    seed: i64,
+}
+
+impl FillArgs {
+    fn values(&self) -> impl Iterator<Item = i64> {
+        fill(self.stencil.clone(), self.aggregate, self.seed)
+    }
+}
+
+impl Command for Args {
+    fn execute(&self) -> Result<String> {
+        match self {
+            Self::Part1 { square } => part_one(number_input("day03", *square)?),
+            Self::Part2 { limit } => part_two(number_input("day03", *limit)?),
+            Self::Render {
+                count,
+                values,
+                fill,
+            } => Ok(match values {
+                Values::Index => render((1..).take(*count)),
+                Values::Sums => render(fill.values().take(*count)),
+            }),
+            Self::Sequence { count, fill } => Ok(fill
+                .values()
+                .take(*count)
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Values {
+    /// The square numbers from part one
+    Index,
+    /// The stencil fill, part two's neighbour sums by default
+    Sums,
+}
+
+impl FromStr for Values {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "index" => Ok(Self::Index),
+            "sums" => Ok(Self::Sums),
+            _ => Err(anyhow!("Unknown values: {}", s)),
+        }
+    }
+}
+
+fn distance_from_port(pos: Point) -> i64 {
+    pos.manhattan(Point::default())
+}
+
+struct SpiralIter {
+    direction: Direction,
+    current: Option<Point>,
+}
+
+impl SpiralIter {
+    fn new() -> Self {
+        Self {
+            direction: Direction::Right,
+            current: None,
+        }
+    }
+}
+
+impl Iterator for SpiralIter {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pos) = self.current {
+            let new_pos = pos + self.direction.delta();
+
+            // Turn once the new square is on the corner diagonal of the ring it is walking
+            let turn = match self.direction {
+                Direction::Right => new_pos.x > new_pos.y,
+                Direction::Up | Direction::Down => new_pos.y == -new_pos.x,
+                Direction::Left => new_pos.x == new_pos.y,
+            };
+
+            if turn {
+                self.direction = self.direction.turn_left();
+            }
+
+            self.current = Some(new_pos);
+        } else {
+            self.current = Some(Point::new(0, 0));
+        }
+
+        self.current
+    }
+}
+
+/// The position of the square `index` steps along the spiral, without walking it
+///
+/// Ring `k` is the square of side `2k + 1` around the port, holding the squares after
+/// `(2k - 1)^2` up to `(2k + 1)^2`, and is walked up, left, down then right.
+fn spiral_point(index: usize) -> Point {
+    let square = index as i64 + 1;
+    let ring = (((square - 1) as u64).isqrt() as i64 + 1) / 2;
+
+    if ring == 0 {
+        return Point::default();
+    }
+
+    let side = 2 * ring;
+    let along = square - (side - 1) * (side - 1) - 1;
+
+    match along / side {
+        0 => Point::new(ring, ring - 1 - along),
+        1 => Point::new(ring - 1 - (along - side), -ring),
+        2 => Point::new(-ring, -ring + 1 + (along - 2 * side)),
+        _ => Point::new(-ring + 1 + (along - 3 * side), ring),
+    }
+}
+
+/// The number of steps along the spiral to reach `pos`, the inverse of `spiral_point`, or
+/// `None` if that's too many to count
+fn spiral_index(pos: Point) -> Option<usize> {
+    let ring = pos.x.checked_abs()?.max(pos.y.checked_abs()?);
+
+    if ring == 0 {
+        return Some(0);
+    }
+
+    let side = ring.checked_mul(2)?;
+    let start = (side - 1).checked_mul(side - 1)?;
+
+    // Every square of the ring is less than `4 * side` past `start`, so if that fits, so does
+    // everything below
+    start.checked_add(side.checked_mul(4)?)?;
+    let along = if pos.x == ring && pos.y < ring {
+        ring - 1 - pos.y
+    } else if pos.y == -ring && pos.x < ring {
+        side + ring - 1 - pos.x
+    } else if pos.x == -ring {
+        2 * side + pos.y + ring - 1
+    } else {
+        3 * side + pos.x + ring - 1
+    };
+
+    usize::try_from(start + along).ok()
+}
+
+fn part_one(square: usize) -> Result<String> {
+    if square == 0 {
+        return Err(anyhow!("Squares are numbered from 1"));
+    }
+
+    Ok(distance_from_port(spiral_point(square - 1)).to_string())
+}
+
+/// The squares whose values feed into a newly filled square, relative to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stencil {
+    Orthogonal,
+    Surrounding,
+    Knight,
+    Custom(Vec<Point>),
+}
+
+impl Stencil {
+    fn offsets(&self) -> Vec<Point> {
+        match self {
+            Self::Orthogonal => Point::default().neighbors4().collect(),
+            Self::Surrounding => Point::default().neighbors8().collect(),
+            Self::Knight => [
+                (1, -2),
+                (2, -1),
+                (2, 1),
+                (1, 2),
+                (-1, 2),
+                (-2, 1),
+                (-2, -1),
+                (-1, -2),
+            ]
+            .iter()
+            .map(|(x, y)| Point::new(*x, *y))
+            .collect(),
+            Self::Custom(offsets) => offsets.clone(),
+        }
+    }
+}
+
+impl FromStr for Stencil {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "4" => Ok(Self::Orthogonal),
+            "8" => Ok(Self::Surrounding),
+            "knight" => Ok(Self::Knight),
+            _ => Ok(Self::Custom(
+                s.split(';')
+                    .map(|offset| offset.parse())
+                    .collect::<Result<_>>()
+                    .map_err(|err| anyhow!("Unknown stencil {}: {}", s, err))?,
+            )),
+        }
+    }
+}
+
+/// How the values under the stencil combine into the new square's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Product,
+    Max,
+    Count,
+}
+
+impl Aggregate {
+    /// Combine `values`, or `None` on overflow; no values at all give the operation's identity
+    fn apply(self, mut values: impl Iterator<Item = i64>) -> Option<i64> {
+        match self {
+            Self::Sum => values.try_fold(0i64, |total, value| total.checked_add(value)),
+            Self::Product => values.try_fold(1i64, |total, value| total.checked_mul(value)),
+            Self::Max => Some(values.max().unwrap_or(0)),
+            Self::Count => Some(values.count() as i64),
+        }
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sum" => Ok(Self::Sum),
+            "product" => Ok(Self::Product),
+            "max" => Ok(Self::Max),
+            "count" => Ok(Self::Count),
+            _ => Err(anyhow!("Unknown aggregate: {}", s)),
+        }
+    }
+}
+
+/// Fill the spiral from `seed`, each square combining the already filled squares under the
+/// stencil; ends if a value overflows
+fn fill(stencil: Stencil, aggregate: Aggregate, seed: i64) -> impl Iterator<Item = i64> {
+    let offsets = stencil.offsets();
+
+    // Squares are filled in spiral order, so `spiral_index` doubles as the slot in `values`
+    SpiralIter::new()
+        .enumerate()
+        .scan(Vec::with_capacity(1024), move |values, (index, pos)| {
+            debug!(pos = ?pos);
+
+            let value = if index == 0 {
+                seed
+            } else {
+                aggregate.apply(
+                    offsets
+                        .iter()
+                        .filter_map(|offset| pos.checked_add(*offset))
+                        .filter_map(spiral_index)
+                        .filter(|neighbor| *neighbor < index)
+                        .map(|neighbor| values[neighbor]),
+                )?
+            };
+
+            values.push(value);
+
+            Some(value)
+        })
+}
+
+fn surrounding_values() -> impl Iterator<Item = i64> {
+    fill(Stencil::Surrounding, Aggregate::Sum, 1)
+}
+
+/// Lay `values` out along the spiral as right-aligned columns, blank where the spiral hasn't
+/// reached yet
+fn render(values: impl Iterator<Item = i64>) -> String {
+    let squares = SpiralIter::new().zip(values).collect::<HashMap<_, _>>();
+    let bounds = match Rect::bounding(squares.keys().copied()) {
+        Some(bounds) => bounds,
+        None => return String::new(),
+    };
+    let width = squares
+        .values()
+        .map(|value| value.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    bounds
+        .rows()
+        .map(|row| {
+            row.map(|pos| match squares.get(&pos) {
+                Some(value) => format!("{:>width$}", value, width = width),
+                None => " ".repeat(width),
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn part_two(limit: i64) -> Result<String> {
+    surrounding_values()
+        .find(|x| *x > limit)
+        .map(|x| x.to_string())
+        .ok_or_else(|| anyhow!("No value larger than {} before the sums overflow", limit))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_one() -> Result<()> {
+        /*
+        17  16  15  14  13
+        18   5   4   3  12
+        19   6   1   2  11
+        20   7   8   9  10
+        21  22  23---> ...
+
+        For example:
+
+            Data from square 1 is carried 0 steps, since it's at the access port.
+            Data from square 12 is carried 3 steps, such as: down, left, left.
+            Data from square 23 is carried only 2 steps: up twice.
+            Data from square 1024 must be carried 31 steps.
+        */
+        assert_eq!(distance_from_port(Point::new(0, 0)), 0);
+        assert_eq!(distance_from_port(Point::new(2, -1)), 3);
+        assert_eq!(distance_from_port(Point::new(0, 2)), 2);
+
+        assert_eq!(distance_from_port(SpiralIter::new().next().unwrap()), 0);
+
+        assert_eq!(distance_from_port(SpiralIter::new().nth(11).unwrap()), 3);
+
+        assert_eq!(distance_from_port(SpiralIter::new().nth(22).unwrap()), 2);
+
+        assert_eq!(distance_from_port(SpiralIter::new().nth(1023).unwrap()), 31);
+
+        assert_eq!(part_one(1024)?, "31");
+        assert!(part_one(0).is_err());
+
+        Ok(())
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_spiral_iter() -> Result<()> {
+        let expected = vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(1, -1),
+            Point::new(0, -1),
+            Point::new(-1, -1),
+            Point::new(-1, 0),
+            Point::new(-1, 1),
+            Point::new(0, 1),
+            Point::new(1, 1),
+            Point::new(2, 1),
+        ];
+
+        let actual: Vec<_> = SpiralIter::new().take(expected.len()).collect();
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() {
+        let expected = "\
+17  16  15  14  13
+18   5   4   3  12
+19   6   1   2  11
+20   7   8   9  10
+21  22  23";
+
+        assert_eq!(render((1..).take(23)), expected);
+
+        let expected = "\
+147  142  133  122   59
+304    5    4    2   57
+330   10    1    1   54
+351   11   23   25   26
+362  747  806";
+
+        assert_eq!(render(surrounding_values().take(23)), expected);
+
+        assert_eq!(render((1..).take(6)), "5  4  3\n6  1  2");
+        assert_eq!(render(std::iter::empty()), "");
+    }
+
+    #[test]
+    fn test_spiral_closed_form() {
+        for (index, pos) in SpiralIter::new().enumerate().take(100_000) {
+            assert_eq!(spiral_point(index), pos);
+            assert_eq!(spiral_index(pos), Some(index));
+        }
+
+        assert_eq!(spiral_index(Point::new(3_000_000_000, 0)), None);
+        assert_eq!(spiral_index(Point::new(0, i64::MIN)), None);
+
+        let far = Point::new(-1_000_000_000, 5);
+        assert_eq!(spiral_index(far).map(spiral_point), Some(far));
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_two() -> Result<()> {
+        /*
+        147  142  133  122   59
+        304    5    4    2   57
+        330   10    1    1   54
+        351   11   23   25   26
+        362  747  806--->   ...
+        */
+        let expected = vec![
+            1, 1, 2, 4, 5, 10, 11, 23, 25, 26, 54, 57, 59, 122, 133, 142, 147, 304, 330, 351, 362,
+            747, 806,
+        ];
+
+        assert_eq!(
+            surrounding_values()
+                .take(expected.len())
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        assert_eq!(part_two(800)?, "806");
+        assert!(part_two(i64::MAX).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill() -> Result<()> {
+        // The start of OEIS A141481, which is part two's sequence
+        let a141481 = vec![
+            1, 1, 2, 4, 5, 10, 11, 23, 25, 26, 54, 57, 59, 122, 133, 142, 147, 304, 330, 351, 362,
+            747, 806, 880, 931, 957, 1968, 2105, 2275, 2391, 2450, 5022, 5336, 5733, 6155, 6444,
+            6591, 13486, 14267, 15252, 16295, 17008, 17370, 35487, 37402, 39835, 42452, 45220,
+            47108, 48065, 98098, 103128, 109476, 116247, 123363, 128204, 130654, 266330, 279138,
+            295229, 312453, 330785, 349975, 363010, 369601,
+        ];
+
+        assert_eq!(
+            fill(Stencil::Surrounding, Aggregate::Sum, 1)
+                .take(a141481.len())
+                .collect::<Vec<_>>(),
+            a141481
+        );
+        assert_eq!(
+            fill("4".parse()?, Aggregate::Sum, 1)
+                .take(10)
+                .collect::<Vec<_>>(),
+            vec![1, 1, 1, 2, 2, 3, 3, 4, 5, 5]
+        );
+        assert_eq!(
+            fill(Stencil::Surrounding, Aggregate::Count, 1)
+                .take(10)
+                .collect::<Vec<_>>(),
+            vec![1, 1, 2, 3, 2, 3, 2, 4, 3, 2]
+        );
+
+        // Knights never reach back into the first ring, so those squares are all empty sums
+        assert!(fill(Stencil::Knight, Aggregate::Sum, 1)
+            .skip(1)
+            .take(8)
+            .all(|value| value == 0));
+
+        // Neighbours too far away to number are never filled, so they're skipped
+        for stencil in [
+            "5000000000,0",
+            "9223372036854775807,0",
+            "0,-9223372036854775808",
+        ]
+        .iter()
+        {
+            assert_eq!(
+                fill(stencil.parse()?, Aggregate::Sum, 1)
+                    .take(3)
+                    .collect::<Vec<_>>(),
+                vec![1, 0, 0]
+            );
+        }
+
+        // The same as the orthogonal stencil, just given explicitly
+        assert_eq!(
+            fill("0,-1;1,0;0,1;-1,0".parse()?, Aggregate::Max, 3)
+                .take(5)
+                .collect::<Vec<_>>(),
+            vec![3, 3, 3, 3, 3]
+        );
+
+        // Doubling products overflow eventually, ending the sequence
+        assert!(fill(Stencil::Surrounding, Aggregate::Product, 2).count() < 200);
+        assert!("1,x".parse::<Stencil>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_number_input() -> Result<()> {
+        assert_eq!(number_input("day00", Some(12usize))?, 12);
+
+        let err = number_input::<usize>("day00", None).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("No input given on the command line"));
+
+        Ok(())
+    }
+}
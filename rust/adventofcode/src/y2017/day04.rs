@@ -0,0 +1,920 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::time::Instant;
+
+use anyhow::{anyhow, Error, Result};
+use clap::Clap;
+
+use super::input;
+use crate::rng::XorShift;
+use crate::Command;
+
+#[derive(Debug, Clap)]
+pub enum Args {
+    Part1 {
+        /// Show why each rejected passphrase failed
+        #[clap(long)]
+        explain: bool,
+    },
+    Part2 {
+        /// Show why each rejected passphrase failed
+        #[clap(long)]
+        explain: bool,
+    },
+    /// Count the passphrases meeting a custom policy
+    Check {
+        #[clap(flatten)]
+        policy: PolicyArgs,
+
+        /// Show why each rejected passphrase failed
+        #[clap(long)]
+        explain: bool,
+    },
+    /// Time anagram and near-duplicate detection on a generated passphrase list
+    Bench {
+        #[clap(default_value = "100000")]
+        lines: usize,
+    },
+}
+
+impl Command for Args {
+    fn execute(&self) -> Result<String> {
+        let (policy, explain) = match self {
+            Self::Part1 { explain } => (Policy::new(vec![Rule::NoDuplicates]), explain),
+            Self::Part2 { explain } => (
+                Policy::new(vec![Rule::NoDuplicates, Rule::NoAnagrams]),
+                explain,
+            ),
+            Self::Check { policy, explain } => (policy.policy(), explain),
+            Self::Bench { lines } => return Ok(benchmark(*lines)),
+        };
+
+        let input = input("day04")?;
+
+        Ok(if *explain {
+            policy.explain(&input)
+        } else {
+            policy.count_valid(&input).to_string()
+        })
+    }
+}
+
+#[derive(Debug, Clap)]
+pub struct PolicyArgs {
+    /// Reject passphrases that repeat a word
+    #[clap(long)]
+    no_duplicates: bool,
+
+    /// Reject passphrases with two words that are anagrams of each other
+    #[clap(long)]
+    no_anagrams: bool,
+
+    /// Reject passphrases containing a word that reads the same backwards
+    #[clap(long)]
+    no_palindromes: bool,
+
+    /// Reject passphrases with fewer words than this
+    #[clap(long)]
+    min_words: Option<usize>,
+
+    /// Reject passphrases using characters other than these
+    #[clap(long)]
+    charset: Option<String>,
+
+    /// Reject passphrases with two different words within this many edits of each other
+    #[clap(long)]
+    near_duplicates: Option<usize>,
+
+    /// How to count edits for --near-duplicates
+    #[clap(long, default_value = "levenshtein", possible_values = Metric::NAMES)]
+    metric: Metric,
+
+    /// Compare words and characters without regard to case
+    #[clap(long)]
+    ignore_case: bool,
+}
+
+impl PolicyArgs {
+    fn policy(&self) -> Policy {
+        let mut rules = Vec::new();
+
+        if self.no_duplicates {
+            rules.push(Rule::NoDuplicates);
+        }
+
+        if self.no_anagrams {
+            rules.push(Rule::NoAnagrams);
+        }
+
+        if self.no_palindromes {
+            rules.push(Rule::NoPalindromes);
+        }
+
+        if let Some(count) = self.min_words {
+            rules.push(Rule::MinWords(count));
+        }
+
+        if let Some(charset) = &self.charset {
+            rules.push(Rule::Charset(charset.clone()));
+        }
+
+        if let Some(distance) = self.near_duplicates {
+            rules.push(Rule::NearDuplicates {
+                distance,
+                metric: self.metric,
+            });
+        }
+
+        Policy::new(rules).ignore_case(self.ignore_case)
+    }
+}
+
+/// One requirement a passphrase has to meet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    NoDuplicates,
+    NoAnagrams,
+    /// Words of more than one character may not read the same backwards
+    NoPalindromes,
+    MinWords(usize),
+    /// Every character must be one of these
+    Charset(String),
+    /// No two different words may be within `distance` edits of each other
+    NearDuplicates {
+        distance: usize,
+        metric: Metric,
+    },
+}
+
+impl Rule {
+    /// The words breaking the rule, or `None` if the passphrase meets it
+    ///
+    /// `keys` are the words as they should be compared, e.g. lowercased.
+    fn conflict(&self, words: &[&str], keys: &[String]) -> Option<Vec<String>> {
+        let pick = |indexes: Vec<usize>| indexes.into_iter().map(|i| words[i].to_string());
+
+        match self {
+            Self::NoDuplicates => {
+                first_pair(keys, |key| key.clone()).map(|pair| pick(pair).collect())
+            }
+            Self::NoAnagrams => {
+                first_pair(keys, |key| Signature::of(key)).map(|pair| pick(pair).collect())
+            }
+            Self::NoPalindromes => {
+                let palindromes = (0..keys.len())
+                    .filter(|i| {
+                        let key = &keys[*i];
+                        key.chars().count() > 1 && key.chars().eq(key.chars().rev())
+                    })
+                    .collect::<Vec<_>>();
+
+                if palindromes.is_empty() {
+                    None
+                } else {
+                    Some(pick(palindromes).collect())
+                }
+            }
+            Self::MinWords(count) => {
+                if words.len() < *count {
+                    Some(pick((0..words.len()).collect()).collect())
+                } else {
+                    None
+                }
+            }
+            Self::Charset(charset) => {
+                let outside = (0..keys.len())
+                    .filter(|i| keys[*i].chars().any(|c| !charset.contains(c)))
+                    .collect::<Vec<_>>();
+
+                if outside.is_empty() {
+                    None
+                } else {
+                    Some(pick(outside).collect())
+                }
+            }
+            Self::NearDuplicates { distance, metric } => {
+                closest_pair(keys, *distance, *metric).map(|pair| pick(pair).collect())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoDuplicates => write!(f, "no-duplicates"),
+            Self::NoAnagrams => write!(f, "no-anagrams"),
+            Self::NoPalindromes => write!(f, "no-palindromes"),
+            Self::MinWords(count) => write!(f, "min-words {}", count),
+            Self::Charset(charset) => write!(f, "charset {:?}", charset),
+            Self::NearDuplicates { distance, metric } => {
+                write!(f, "near-duplicates {} ({})", distance, metric)
+            }
+        }
+    }
+}
+
+/// The indexes of the first two keys that are the same under `key`
+fn first_pair<K, F>(keys: &[String], key: F) -> Option<Vec<usize>>
+where
+    K: Hash + Eq,
+    F: Fn(&String) -> K,
+{
+    let mut seen = HashMap::new();
+
+    keys.iter()
+        .enumerate()
+        .find_map(|(i, k)| seen.insert(key(k), i).map(|first| vec![first, i]))
+}
+
+/// How edits between two words are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Insertions, deletions and substitutions
+    Levenshtein,
+    /// Levenshtein, plus swapping two adjacent characters
+    ///
+    /// This is the optimal string alignment variant, which never edits a swapped pair again.
+    Damerau,
+}
+
+impl Metric {
+    const NAMES: &'static [&'static str] = &["levenshtein", "damerau"];
+}
+
+impl FromStr for Metric {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "levenshtein" => Ok(Self::Levenshtein),
+            "damerau" => Ok(Self::Damerau),
+            _ => Err(anyhow!("Unknown metric: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::NAMES[*self as usize])
+    }
+}
+
+/// The edit distance from `a` to `b`, or `None` if it's more than `max`
+///
+/// Only the band of the table within `max` of the diagonal is filled in, and the search
+/// stops as soon as a whole row is over `max`, so far apart words are rejected quickly.
+fn edit_distance(a: &[char], b: &[char], max: usize, metric: Metric) -> Option<usize> {
+    let max = max.min(a.len().max(b.len()));
+
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    // Anything over `max` is as good as infinite, so every cell is capped at this
+    let over = max + 1;
+    let width = b.len() + 1;
+
+    let mut before = vec![over; width];
+    let mut previous = (0..width).map(|j| j.min(over)).collect::<Vec<_>>();
+    let mut current = vec![over; width];
+
+    for i in 1..=a.len() {
+        let from = i.saturating_sub(max).max(1);
+        let to = (i + max).min(b.len());
+
+        // Cells just outside the band are read by the next two rows, and may be stale
+        current[0] = i.min(over);
+        for cell in current[from.saturating_sub(2).max(1)..from].iter_mut() {
+            *cell = over;
+        }
+        if to < b.len() {
+            current[to + 1] = over;
+        }
+
+        let mut lowest = if from == 1 { current[0] } else { over };
+
+        for j in from..=to {
+            let substitute = previous[j - 1] + (a[i - 1] != b[j - 1]) as usize;
+            let mut cell = substitute.min(previous[j] + 1).min(current[j - 1] + 1);
+
+            if metric == Metric::Damerau
+                && i > 1
+                && j > 1
+                && a[i - 1] == b[j - 2]
+                && a[i - 2] == b[j - 1]
+            {
+                cell = cell.min(before[j - 2] + 1);
+            }
+
+            current[j] = cell.min(over);
+            lowest = lowest.min(current[j]);
+        }
+
+        if lowest > max {
+            return None;
+        }
+
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    Some(previous[b.len()]).filter(|distance| *distance <= max)
+}
+
+/// The indexes of the two closest different keys, if they're within `max` edits
+///
+/// Ties go to the pair that comes first in the line.
+fn closest_pair(keys: &[String], max: usize, metric: Metric) -> Option<Vec<usize>> {
+    let chars = keys
+        .iter()
+        .map(|key| key.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // In length order, each key only has to be compared with those up to `max` longer
+    let mut order = (0..keys.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| chars[*i].len());
+
+    let mut closest: Option<(usize, usize, usize)> = None;
+
+    for (n, i) in order.iter().enumerate() {
+        for j in order[n + 1..].iter() {
+            if chars[*j].len() - chars[*i].len() > max {
+                break;
+            }
+
+            // Nothing further away than the closest pair so far can replace it
+            let bound = closest.map_or(max, |(distance, _, _)| distance);
+
+            if let Some(distance) =
+                edit_distance(&chars[*i], &chars[*j], bound, metric).filter(|d| *d > 0)
+            {
+                let pair = (distance, *i.min(j), *i.max(j));
+
+                if closest.is_none_or(|closest| pair < closest) {
+                    closest = Some(pair);
+                }
+            }
+        }
+    }
+
+    closest.map(|(_, i, j)| vec![i, j])
+}
+
+/// A rule a passphrase broke, and the words that broke it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    rule: Rule,
+    words: Vec<String>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.rule {
+            Rule::MinWords(_) => write!(f, "{}: only {} words", self.rule, self.words.len()),
+            _ => write!(f, "{}: {}", self.rule, self.words.join(", ")),
+        }
+    }
+}
+
+/// The rules every passphrase has to meet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    rules: Vec<Rule>,
+    ignore_case: bool,
+}
+
+impl Policy {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self {
+            rules,
+            ignore_case: false,
+        }
+    }
+
+    /// Compare words, and check characters against the charset, case insensitively
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+
+        if ignore_case {
+            for rule in self.rules.iter_mut() {
+                if let Rule::Charset(charset) = rule {
+                    *charset = charset.to_lowercase();
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Every rule `line` breaks
+    pub fn check(&self, line: &str) -> Vec<Violation> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let keys = words
+            .iter()
+            .map(|word| {
+                if self.ignore_case {
+                    word.to_lowercase()
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.rules
+            .iter()
+            .filter_map(|rule| {
+                rule.conflict(&words, &keys).map(|words| Violation {
+                    rule: rule.clone(),
+                    words,
+                })
+            })
+            .collect()
+    }
+
+    pub fn is_valid(&self, line: &str) -> bool {
+        self.check(line).is_empty()
+    }
+
+    pub fn count_valid(&self, input: &str) -> usize {
+        input.lines().filter(|line| self.is_valid(line)).count()
+    }
+
+    /// Every rejected line with the rules it broke, followed by the number of valid lines
+    pub fn explain(&self, input: &str) -> String {
+        let mut valid = 0;
+        let mut report = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let violations = self.check(line);
+
+            if violations.is_empty() {
+                valid += 1;
+                continue;
+            }
+
+            report.push(format!("{}: {}", i + 1, line));
+            report.extend(
+                violations
+                    .iter()
+                    .map(|violation| format!("    {}", violation)),
+            );
+        }
+
+        report.push(format!("{} valid", valid));
+        report.join("\n")
+    }
+}
+
+/// A key shared by a word and its anagrams, and nothing else
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Signature {
+    /// How many of each lowercase ASCII letter the word has
+    Letters([u8; 26]),
+    /// The word's bytes, sorted, for any other ASCII word
+    Bytes(Vec<u8>),
+    /// The word's characters, sorted, for anything else
+    Chars(Vec<char>),
+}
+
+impl Signature {
+    fn of(word: &str) -> Self {
+        let bytes = word.as_bytes();
+
+        // Counts can't overflow a `u8` with fewer than 256 letters
+        if bytes.len() < 256 && bytes.iter().all(u8::is_ascii_lowercase) {
+            let mut counts = [0u8; 26];
+
+            for byte in bytes {
+                counts[(byte - b'a') as usize] += 1;
+            }
+
+            Self::Letters(counts)
+        } else {
+            Self::sorted(word)
+        }
+    }
+
+    /// The fallback signature, which works for any word
+    fn sorted(word: &str) -> Self {
+        if word.is_ascii() {
+            let mut bytes = word.as_bytes().to_vec();
+            bytes.sort_unstable();
+            Self::Bytes(bytes)
+        } else {
+            let mut chars = word.chars().collect::<Vec<_>>();
+            chars.sort_unstable();
+            Self::Chars(chars)
+        }
+    }
+}
+
+/// Lines of random words over a few letters, so anagrams and duplicates are common
+fn benchmark_passphrases(lines: usize) -> String {
+    let mut rng = XorShift::new(XorShift::SEED);
+    let mut random = move |bound| rng.below(bound);
+
+    (0..lines)
+        .map(|_| {
+            (0..5 + random(8))
+                .map(|_| {
+                    (0..2 + random(7))
+                        .map(|_| (b'a' + random(6) as u8) as char)
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Time signing every word of a generated passphrase list, the whole part two policy, and a
+/// near-duplicate policy
+fn benchmark(lines: usize) -> String {
+    let input = benchmark_passphrases(lines);
+    let words = input.split_whitespace().collect::<Vec<_>>();
+
+    let time = |sign: fn(&str) -> Signature| {
+        let start = Instant::now();
+        let signatures = words.iter().map(|word| sign(word)).collect::<Vec<_>>();
+        (start.elapsed(), signatures.len())
+    };
+
+    let (letters, _) = time(Signature::of);
+    let (sorted, _) = time(Signature::sorted);
+
+    let start = Instant::now();
+    let valid = Policy::new(vec![Rule::NoDuplicates, Rule::NoAnagrams]).count_valid(&input);
+    let policy = start.elapsed();
+
+    let start = Instant::now();
+    let near = Policy::new(vec![Rule::NearDuplicates {
+        distance: 2,
+        metric: Metric::Damerau,
+    }])
+    .count_valid(&input);
+    let near_duplicates = start.elapsed();
+
+    format!(
+        "{} lines, {} words, {} valid\nletter counts: {:?}\nsorted:        {:?}\npolicy:        {:?}\nnear-duplicates 2 (damerau): {} valid in {:?}",
+        lines,
+        words.len(),
+        valid,
+        letters,
+        sorted,
+        policy,
+        near,
+        near_duplicates
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn has_duplicate_words(line: &str) -> bool {
+        !Policy::new(vec![Rule::NoDuplicates]).is_valid(line)
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct CharCount {
+        what: char,
+        count: usize,
+    }
+
+    impl CharCount {
+        fn new(what: char, count: usize) -> Self {
+            Self { what, count }
+        }
+    }
+
+    /// How many of each character `word` has, which is the same for all of its anagrams
+    fn char_counts(word: &str) -> Vec<CharCount> {
+        let mut char_counts: HashMap<char, usize> = HashMap::new();
+
+        word.chars().for_each(|c| {
+            char_counts.entry(c).and_modify(|n| *n += 1).or_insert(1);
+        });
+
+        let mut counts = char_counts
+            .into_iter()
+            .map(|(c, n)| CharCount::new(c, n))
+            .collect::<Vec<_>>();
+        counts.sort_by_key(|cc| cc.what);
+        counts
+    }
+
+    /// The original check for part two, kept to compare `Rule::NoAnagrams` against
+    fn has_anagrams(line: &str) -> bool {
+        let mut counter: HashSet<Vec<CharCount>> = HashSet::new();
+
+        let found = line
+            .split(' ')
+            .map(char_counts)
+            .find(|word| !counter.insert(word.clone()));
+
+        found.is_some()
+    }
+
+    /// The whole edit distance table, with no bound
+    fn full_edit_distance(a: &str, b: &str, metric: Metric) -> usize {
+        let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+        let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+        for i in 0..=a.len() {
+            for j in 0..=b.len() {
+                table[i][j] = if i == 0 || j == 0 {
+                    i + j
+                } else {
+                    let cost = (a[i - 1] != b[j - 1]) as usize;
+                    let mut cell = (table[i - 1][j - 1] + cost)
+                        .min(table[i - 1][j] + 1)
+                        .min(table[i][j - 1] + 1);
+
+                    if metric == Metric::Damerau
+                        && i > 1
+                        && j > 1
+                        && a[i - 1] == b[j - 2]
+                        && a[i - 2] == b[j - 1]
+                    {
+                        cell = cell.min(table[i - 2][j - 2] + 1);
+                    }
+
+                    cell
+                };
+            }
+        }
+
+        table[a.len()][b.len()]
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_has_duplicate_words() -> Result<()> {
+        /*
+        For example:
+
+            aa bb cc dd ee is valid.
+            aa bb cc dd aa is not valid - the word aa appears more than once.
+            aa bb cc dd aaa is valid - aa and aaa count as different words.
+        */
+
+        assert!(!has_duplicate_words("aa bb cc dd ee"));
+        assert!(has_duplicate_words("aa bb cc dd aa"));
+        assert!(!has_duplicate_words("aa bb cc dd aaa"));
+
+        Ok(())
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_get_char_counts() -> Result<()> {
+        /*
+        For example:
+
+            abcde fghij is a valid passphrase.
+            abcde xyz ecdab is not valid - the letters from the third word can be rearranged to form the first word.
+            a ab abc abd abf abj is a valid passphrase, because all letters need to be used when forming another word.
+            iiii oiii ooii oooi oooo is valid.
+            oiii ioii iioi iiio is not valid - any of these words can be rearranged to form any other word.
+        */
+
+        assert!(!has_anagrams("abcde fghij"));
+        assert!(has_anagrams("abcde xyz ecdab"));
+        assert!(!has_anagrams("a ab abc abd abf abj"));
+        assert!(!has_anagrams("iiii oiii ooii oooi oooo"));
+        assert!(has_anagrams("oiii ioii iioi iiio"));
+
+        let policy = Policy::new(vec![Rule::NoAnagrams]);
+
+        for line in [
+            "abcde fghij",
+            "abcde xyz ecdab",
+            "a ab abc abd abf abj",
+            "iiii oiii ooii oooi oooo",
+            "oiii ioii iioi iiio",
+        ]
+        .iter()
+        {
+            assert_eq!(policy.is_valid(line), !has_anagrams(line));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_signature() {
+        let words = [
+            "", "a", "ab", "ba", "abc", "cab", "aab", "abb", "Ab", "bA", "b-a", "a-b", "été",
+            "tée", "ét", "té", "ÿz", "zÿ",
+        ];
+
+        for l in words.iter() {
+            for r in words.iter() {
+                assert_eq!(
+                    Signature::of(l) == Signature::of(r),
+                    char_counts(l) == char_counts(r),
+                    "{} {}",
+                    l,
+                    r
+                );
+            }
+        }
+
+        let long = "a".repeat(300);
+        assert_eq!(Signature::of(&long), Signature::sorted(&long));
+        assert_ne!(Signature::of(&long), Signature::of(&"a".repeat(299)));
+    }
+
+    #[test]
+    fn test_signature_reference() {
+        let policy = Policy::new(vec![Rule::NoAnagrams]);
+        let input = benchmark_passphrases(2000);
+
+        for line in input.lines() {
+            assert_eq!(policy.is_valid(line), !has_anagrams(line), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = Policy::new(vec![Rule::NoDuplicates, Rule::NoAnagrams]);
+
+        assert!(policy.is_valid("abcde fghij"));
+        assert!(!policy.is_valid("abcde xyz ecdab"));
+        assert!(!policy.is_valid("aa bb aa"));
+        assert_eq!(
+            policy.check("aa bb aa"),
+            vec![
+                Violation {
+                    rule: Rule::NoDuplicates,
+                    words: vec!["aa".into(), "aa".into()]
+                },
+                Violation {
+                    rule: Rule::NoAnagrams,
+                    words: vec!["aa".into(), "aa".into()]
+                }
+            ]
+        );
+
+        let policy = Policy::new(vec![
+            Rule::NoPalindromes,
+            Rule::MinWords(3),
+            Rule::Charset("abcdefghijklmnopqrstuvwxyz".into()),
+        ]);
+
+        assert!(policy.is_valid("abc def a"));
+        assert_eq!(
+            policy
+                .check("abba Noon")
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "no-palindromes: abba",
+                "min-words 3: only 2 words",
+                "charset \"abcdefghijklmnopqrstuvwxyz\": Noon"
+            ]
+        );
+
+        let policy = policy.ignore_case(true);
+        assert_eq!(
+            policy
+                .check("abba Noon")
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>(),
+            vec!["no-palindromes: abba, Noon", "min-words 3: only 2 words"]
+        );
+
+        let policy = Policy::new(vec![Rule::NoDuplicates]).ignore_case(true);
+        assert!(!policy.is_valid("Aa bb aA"));
+        assert!(Policy::new(vec![Rule::NoDuplicates]).is_valid("Aa bb aA"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let distance = |a: &str, b: &str, max, metric| {
+            let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+            edit_distance(&a, &b, max, metric)
+        };
+
+        assert_eq!(
+            distance("kitten", "sitting", 3, Metric::Levenshtein),
+            Some(3)
+        );
+        assert_eq!(distance("kitten", "sitting", 2, Metric::Levenshtein), None);
+        assert_eq!(distance("abcd", "acbd", 2, Metric::Levenshtein), Some(2));
+        assert_eq!(distance("abcd", "acbd", 2, Metric::Damerau), Some(1));
+        assert_eq!(distance("ca", "abc", 5, Metric::Damerau), Some(3));
+        assert_eq!(distance("", "abc", 3, Metric::Levenshtein), Some(3));
+        assert_eq!(distance("a", "abcd", 2, Metric::Levenshtein), None);
+        assert_eq!(distance("été", "ete", 2, Metric::Levenshtein), Some(2));
+        assert_eq!(distance("same", "same", 0, Metric::Levenshtein), Some(0));
+        assert_eq!(distance("abc", "xyz", usize::MAX, Metric::Damerau), Some(3));
+    }
+
+    #[test]
+    fn test_edit_distance_reference() {
+        let input = benchmark_passphrases(20);
+        let words = input.split_whitespace().collect::<Vec<_>>();
+
+        for a in words.iter() {
+            for b in words.iter() {
+                let a_chars = a.chars().collect::<Vec<_>>();
+                let b_chars = b.chars().collect::<Vec<_>>();
+
+                for metric in [Metric::Levenshtein, Metric::Damerau].iter().copied() {
+                    let expected = full_edit_distance(a, b, metric);
+
+                    for max in 0..5 {
+                        assert_eq!(
+                            edit_distance(&a_chars, &b_chars, max, metric),
+                            Some(expected).filter(|d| *d <= max),
+                            "{} {} {} {:?}",
+                            a,
+                            b,
+                            max,
+                            metric
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_near_duplicates() {
+        let policy = Policy::new(vec![Rule::NearDuplicates {
+            distance: 1,
+            metric: Metric::Damerau,
+        }]);
+
+        assert!(policy.is_valid("abcde fghij aa aa"));
+        assert!(!policy.is_valid("abcd xyz abdc"));
+        assert_eq!(
+            policy
+                .check("pass word wrod passes wordy")
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>(),
+            vec!["near-duplicates 1 (damerau): word, wrod"]
+        );
+
+        let policy = Policy::new(vec![Rule::NearDuplicates {
+            distance: 2,
+            metric: Metric::Levenshtein,
+        }]);
+
+        // `pass` and `passes` are two edits apart, but `word` and `wordy` only one
+        assert_eq!(
+            policy.check("pass word passes wrod wordy")[0].words,
+            vec!["word".to_string(), "wordy".to_string()]
+        );
+        assert!(!policy.ignore_case(true).is_valid("Word wORDS"));
+
+        // Only words of similar lengths are compared, so this doesn't take a million comparisons
+        let line = (1..=1000)
+            .map(|n| "ab".repeat(n))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(Policy::new(vec![Rule::NearDuplicates {
+            distance: 1,
+            metric: Metric::Levenshtein,
+        }])
+        .is_valid(&line));
+
+        for name in Metric::NAMES {
+            assert_eq!(&name.parse::<Metric>().unwrap().to_string(), name);
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let policy = Policy::new(vec![Rule::NoDuplicates, Rule::NoAnagrams]);
+        let input = "aa bb cc\nabcde xyz ecdab\na ab abc";
+
+        assert_eq!(policy.count_valid(input), 2);
+        assert_eq!(
+            policy.explain(input),
+            "2: abcde xyz ecdab\n    no-anagrams: abcde, ecdab\n2 valid"
+        );
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_one() -> Result<()> {
+        Ok(())
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_two() -> Result<()> {
+        Ok(())
+    }
+}
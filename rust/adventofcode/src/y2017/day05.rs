@@ -0,0 +1,223 @@
+use std::fmt;
+
+use anyhow::Result;
+use clap::Clap;
+
+use super::input;
+use crate::Command;
+
+#[derive(Debug, Clap)]
+pub enum Args {
+    Part1 {
+        /// Give up if the jumps haven't exited after this many steps
+        #[clap(long, default_value = "1000000000")]
+        max_steps: u64,
+    },
+    Part2 {
+        /// Give up if the jumps haven't exited after this many steps
+        #[clap(long, default_value = "1000000000")]
+        max_steps: u64,
+    },
+}
+
+impl Command for Args {
+    fn execute(&self) -> Result<String> {
+        match self {
+            Self::Part1 { max_steps } => part_one(*max_steps),
+            Self::Part2 { max_steps } => part_two(*max_steps),
+        }
+    }
+}
+
+/// The longest jump list that is checked for coming back to an earlier state
+const REPETITION_LIMIT: usize = 1024;
+
+/// Why a jump list didn't exit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stuck {
+    /// Still inside the list when the step budget ran out
+    BudgetExhausted(u64),
+    /// The position and offsets after `steps` steps come round again every `period` steps
+    Loops { steps: u64, period: u64 },
+}
+
+impl fmt::Display for Stuck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BudgetExhausted(steps) => write!(f, "Still jumping after {} steps", steps),
+            Self::Loops { steps, period } => write!(
+                f,
+                "Never exits: the state after {} steps repeats every {} steps",
+                steps, period
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Stuck {}
+
+fn increment_offset(offset: i64) -> i64 {
+    offset + 1
+}
+
+fn increment_offset2(offset: i64) -> i64 {
+    if offset >= 3 {
+        offset - 1
+    } else {
+        offset + 1
+    }
+}
+
+fn compile_steps(steps: &str) -> Result<Vec<i64>> {
+    steps
+        .lines()
+        .map(|jump| jump.parse::<i64>().map_err(anyhow::Error::from))
+        .collect()
+}
+
+/// The number of steps it takes to jump out of the list, or why it never did
+///
+/// Gives up after `max_steps`. Lists of up to `REPETITION_LIMIT` jumps are also watched for an
+/// exact repeat of the position and offsets, by Brent's algorithm, so only one earlier state is
+/// kept at a time.
+fn steps_to_exit<F>(steps: &mut [i64], alter_offset_fn: F, max_steps: u64) -> Result<u64, Stuck>
+where
+    F: Fn(i64) -> i64,
+{
+    let mut idx = 0;
+    let mut nsteps = 0;
+
+    let watch = steps.len() <= REPETITION_LIMIT;
+    let mut saved = (idx, steps.to_vec(), nsteps);
+    let mut power = 1;
+
+    loop {
+        if nsteps == max_steps {
+            return Err(Stuck::BudgetExhausted(nsteps));
+        }
+
+        nsteps += 1;
+        let jmp: i64 = steps[idx];
+        let absolute: i64 = idx as i64 + jmp;
+
+        steps[idx] = alter_offset_fn(steps[idx]);
+
+        if absolute < 0 || absolute >= steps.len() as i64 {
+            return Ok(nsteps);
+        }
+
+        idx = absolute as usize;
+
+        if watch {
+            let (saved_idx, saved_steps, saved_at) = &saved;
+
+            if idx == *saved_idx && steps == &saved_steps[..] {
+                return Err(Stuck::Loops {
+                    steps: *saved_at,
+                    period: nsteps - saved_at,
+                });
+            }
+
+            if nsteps - saved_at == power {
+                saved = (idx, steps.to_vec(), nsteps);
+                power *= 2;
+            }
+        }
+    }
+}
+
+fn part_one(max_steps: u64) -> Result<String> {
+    let mut steps = compile_steps(&input("day05")?)?;
+    Ok(steps_to_exit(&mut steps, increment_offset, max_steps)?.to_string())
+}
+
+fn part_two(max_steps: u64) -> Result<String> {
+    let mut steps = compile_steps(&input("day05")?)?;
+    Ok(steps_to_exit(&mut steps, increment_offset2, max_steps)?.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_one() -> Result<()> {
+        let s = "0\n3\n0\n1\n-3";
+
+        let mut steps = compile_steps(s)?;
+        assert_eq!(steps, vec![0, 3, 0, 1, -3]);
+
+        assert_eq!(steps_to_exit(&mut steps, increment_offset, 100), Ok(5));
+
+        Ok(())
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_part_two() -> Result<()> {
+        let s = "0\n3\n0\n1\n-3";
+
+        let mut steps = compile_steps(s)?;
+        assert_eq!(steps, vec![0, 3, 0, 1, -3]);
+
+        assert_eq!(steps_to_exit(&mut steps, increment_offset2, 100), Ok(10));
+        assert_eq!(steps, vec![2, 3, 2, 3, -1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_steps() -> Result<()> {
+        let s = "0\n3\n0\n1\n-3";
+
+        assert_eq!(
+            steps_to_exit(&mut compile_steps(s)?, increment_offset, 5),
+            Ok(5)
+        );
+        assert_eq!(
+            steps_to_exit(&mut compile_steps(s)?, increment_offset, 4),
+            Err(Stuck::BudgetExhausted(4))
+        );
+        assert_eq!(
+            steps_to_exit(&mut compile_steps(s)?, increment_offset, 0),
+            Err(Stuck::BudgetExhausted(0))
+        );
+
+        // Too long to watch for repeats, so only the budget stops it
+        let mut steps = vec![0; REPETITION_LIMIT + 1];
+        assert_eq!(
+            steps_to_exit(&mut steps, |offset| offset, 1000),
+            Err(Stuck::BudgetExhausted(1000))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_loops() {
+        let keep = |offset| offset;
+
+        assert_eq!(
+            steps_to_exit(&mut [0], keep, u64::MAX),
+            Err(Stuck::Loops {
+                steps: 0,
+                period: 1
+            })
+        );
+        assert_eq!(
+            steps_to_exit(&mut [1, -1], keep, u64::MAX),
+            Err(Stuck::Loops {
+                steps: 1,
+                period: 2
+            })
+        );
+
+        // Without changing its offsets, the example never leaves its first jump
+        let result = steps_to_exit(&mut [0, 3, 0, 1, -3], keep, u64::MAX);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Never exits: the state after 0 steps repeats every 1 steps"
+        );
+    }
+}
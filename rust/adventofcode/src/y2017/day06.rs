@@ -3,7 +3,8 @@ use std::collections::HashSet;
 use anyhow::Result;
 use clap::Clap;
 
-use crate::{input, Command};
+use super::input;
+use crate::Command;
 
 #[derive(Debug, Clap)]
 pub enum Args {
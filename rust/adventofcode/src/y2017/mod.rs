@@ -0,0 +1,12 @@
+//! Solutions to the 2017 puzzles
+
+// `new-day` adds a declaration here for every module it creates
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+
+// `YEAR`, the input helpers and the `Solutions` registration, generated by `build.rs`
+include!(concat!(env!("OUT_DIR"), "/y2017.rs"));